@@ -0,0 +1,56 @@
+//! Measures resizing the render buffer while a window edge is dragged back and forth, compared to
+//! resizing a `Vec` like the editor used to. Run with
+//! `cargo bench --bench pixel_buffer_resize --features test-util`.
+
+use nih_plug_slint::slint::Rgb8Pixel;
+use nih_plug_slint::test_util::PixelBuffer;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How often the whole drag is repeated for each measurement.
+const ITERATIONS: u32 = 20;
+
+/// The sizes a window goes through while its edge is dragged back and forth below its initial
+/// size of 1600x1200.
+fn drag_sizes() -> impl Iterator<Item = (u32, u32)> {
+    (200..1600)
+        .step_by(7)
+        .chain((200..1600).step_by(13).rev())
+        .map(|width| (width, width * 3 / 4))
+}
+
+/// Run `f` for the whole drag [`ITERATIONS`] times and return the average time per drag.
+fn measure(mut f: impl FnMut(u32, u32)) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for (width, height) in drag_sizes() {
+            f(width, height);
+        }
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let frames = drag_sizes().count();
+    let background = Rgb8Pixel::new(0x20, 0x20, 0x20);
+
+    // Every size is also drawn to once, like the renderer does after a resize
+    let mut buffer = PixelBuffer::new(1600, 1200);
+    let pixel_buffer = measure(|width, height| {
+        buffer.resize(width, height);
+        buffer.pixels_mut().fill(background);
+        black_box(buffer.pixels());
+    });
+
+    let mut pixels = vec![Rgb8Pixel::default(); 1600 * 1200];
+    let resized_vec = measure(|width, height| {
+        pixels.resize(width as usize * height as usize, Rgb8Pixel::default());
+        pixels.fill(background);
+        black_box(&pixels);
+    });
+
+    println!("Resizing through {frames} sizes per drag:");
+    println!("  pixel buffer: {pixel_buffer:?} per drag");
+    println!("  resized Vec:  {resized_vec:?} per drag");
+}
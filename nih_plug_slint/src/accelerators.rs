@@ -0,0 +1,82 @@
+//! Editor-wide keyboard shortcuts.
+
+use keyboard_types::{Key, KeyboardEvent, Modifiers};
+
+/// A key combination for an editor-wide keyboard shortcut. See
+/// [`SlintState::add_shortcut()`][crate::SlintState::add_shortcut()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlintShortcut {
+    /// The modifiers that need to be held. Modifiers that are not part of this must not be held,
+    /// so Ctrl+S does not trigger on Ctrl+Shift+S.
+    pub modifiers: Modifiers,
+    /// The key that triggers the shortcut. Character keys are matched case-insensitively, so
+    /// `Key::Character("s".into())` matches both `s` and `S`.
+    pub key: Key,
+}
+
+impl SlintShortcut {
+    /// A shortcut for `key` while exactly `modifiers` are held.
+    pub fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// A shortcut for `character` with the platform's command modifier, which is Cmd on macOS and
+    /// Ctrl everywhere else. `SlintShortcut::command('s')` is the usual save shortcut.
+    pub fn command(character: char) -> Self {
+        Self::command_with(Modifiers::empty(), character)
+    }
+
+    /// Like [`command()`][Self::command()], but with additional modifiers, for instance
+    /// `SlintShortcut::command_with(Modifiers::SHIFT, 'z')` for redo.
+    pub fn command_with(modifiers: Modifiers, character: char) -> Self {
+        let command = if cfg!(target_os = "macos") {
+            Modifiers::META
+        } else {
+            Modifiers::CONTROL
+        };
+
+        Self::new(modifiers | command, Key::Character(character.to_string()))
+    }
+
+    /// Whether `event` triggers this shortcut. Releases are never matched.
+    pub(crate) fn matches(&self, event: &KeyboardEvent) -> bool {
+        // Lock keys like Caps Lock and Num Lock are ignored
+        let relevant = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::META;
+        if event.state != keyboard_types::KeyState::Down
+            || event.modifiers & relevant != self.modifiers & relevant
+        {
+            return false;
+        }
+
+        match (&self.key, &event.key) {
+            (Key::Character(expected), Key::Character(actual)) => {
+                expected.to_lowercase() == actual.to_lowercase()
+            }
+            (expected, actual) => expected == actual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcuts_match_exact_modifiers() {
+        let key_event = |modifiers, key: &str| KeyboardEvent {
+            key: Key::Character(key.to_string()),
+            modifiers,
+            ..Default::default()
+        };
+        let shortcut = SlintShortcut::new(Modifiers::CONTROL, Key::Character("s".to_string()));
+
+        assert!(shortcut.matches(&key_event(Modifiers::CONTROL, "s")));
+        assert!(shortcut.matches(&key_event(Modifiers::CONTROL | Modifiers::CAPS_LOCK, "S")));
+        assert!(!shortcut.matches(&key_event(Modifiers::CONTROL | Modifiers::SHIFT, "s")));
+        assert!(!shortcut.matches(&key_event(Modifiers::empty(), "s")));
+        assert!(!shortcut.matches(&KeyboardEvent {
+            state: keyboard_types::KeyState::Up,
+            ..key_event(Modifiers::CONTROL, "s")
+        }));
+    }
+}
@@ -0,0 +1,153 @@
+//! Accessibility preferences that components may want to respect, and screen reader
+//! announcements.
+//!
+//! Slint components have an accessibility tree built from properties like `accessible-role`,
+//! `accessible-label`, and `accessible-value`, but the editor renders through Slint's software
+//! renderer into a plain child window, so that tree is not exposed to the operating system's
+//! accessibility APIs. Screen readers only see an opaque window. Until that is wired up,
+//! [`announce()`] lets a plugin tell screen reader users what changed, for instance with
+//! [`SlintParamContext::announce_value()`][crate::SlintParamContext::announce_value()] when a
+//! control is focused or adjusted.
+
+use crossbeam::atomic::AtomicCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Overrides the system's reduced motion preference when set. See [`set_reduced_motion()`].
+static REDUCED_MOTION_OVERRIDE: AtomicCell<Option<bool>> = AtomicCell::new(None);
+
+/// Incremented whenever [`REDUCED_MOTION_OVERRIDE`] is set, so open editors can tell that they
+/// need to pass the new value to their components.
+static REDUCED_MOTION_OVERRIDE_CHANGES: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether the user prefers reduced motion. Components can use this to disable or shorten
+/// animations for animated meters and transitions. This follows the operating system's
+/// accessibility setting on macOS and Windows unless it has been overridden with
+/// [`set_reduced_motion()`]. On other platforms this is `false` by default.
+///
+/// Slint components can't read this directly. Use
+/// [`SlintState::set_on_reduced_motion_changed()`][set_on_reduced_motion_changed] to mirror it
+/// into a component property that's kept up to date while the editor is open.
+///
+/// [set_on_reduced_motion_changed]: crate::SlintState::set_on_reduced_motion_changed()
+pub fn prefers_reduced_motion() -> bool {
+    REDUCED_MOTION_OVERRIDE
+        .load()
+        .unwrap_or_else(system_prefers_reduced_motion)
+}
+
+/// Override the reduced motion preference returned by [`prefers_reduced_motion()`] for all editors
+/// in this process. Passing `None` goes back to following the system setting. This could for
+/// instance be hooked up to a setting in the plugin's own GUI.
+pub fn set_reduced_motion(reduced_motion: Option<bool>) {
+    REDUCED_MOTION_OVERRIDE.store(reduced_motion);
+    REDUCED_MOTION_OVERRIDE_CHANGES.fetch_add(1, Ordering::Release);
+}
+
+/// The number of times [`set_reduced_motion()`] has been called. This changes whenever the
+/// override may have changed.
+pub(crate) fn reduced_motion_override_changes() -> usize {
+    REDUCED_MOTION_OVERRIDE_CHANGES.load(Ordering::Acquire)
+}
+
+/// Ask the screen reader to read out `text`, like "Gain, -6.0 dB". This is only implemented on
+/// macOS, where VoiceOver speaks the text if it is running and nothing happens otherwise. On other
+/// platforms the announcement is only logged at the debug level. Announcements interrupt each
+/// other, so there's no need to throttle them while a control is being dragged.
+pub fn announce(text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    system_announce(text);
+}
+
+#[cfg(target_os = "macos")]
+fn system_announce(text: &str) {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::CString;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        static NSAccessibilityAnnouncementRequestedNotification: *mut Object;
+        static NSAccessibilityAnnouncementKey: *mut Object;
+
+        fn NSAccessibilityPostNotificationWithUserInfo(
+            element: *mut Object,
+            notification: *mut Object,
+            user_info: *mut Object,
+        );
+    }
+
+    let Ok(text) = CString::new(text) else {
+        return;
+    };
+
+    unsafe {
+        let application: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+        let announcement: *mut Object =
+            msg_send![class!(NSString), stringWithUTF8String: text.as_ptr()];
+        if application.is_null() || announcement.is_null() {
+            return;
+        }
+
+        let user_info: *mut Object = msg_send![
+            class!(NSDictionary),
+            dictionaryWithObject: announcement
+            forKey: NSAccessibilityAnnouncementKey
+        ];
+        NSAccessibilityPostNotificationWithUserInfo(
+            application,
+            NSAccessibilityAnnouncementRequestedNotification,
+            user_info,
+        );
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn system_announce(text: &str) {
+    use crate::logging::debug_log;
+
+    debug_log!("Not announcing '{}' on this platform", text);
+}
+
+#[cfg(target_os = "macos")]
+fn system_prefers_reduced_motion() -> bool {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let workspace: *mut Object = msg_send![class!(NSWorkspace), sharedWorkspace];
+        if workspace.is_null() {
+            return false;
+        }
+
+        msg_send![workspace, accessibilityDisplayShouldReduceMotion]
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn system_prefers_reduced_motion() -> bool {
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    };
+
+    // This is the "Show animations in Windows" setting
+    let mut animations_enabled = BOOL(1);
+    let success = unsafe {
+        SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            Some(&mut animations_enabled as *mut BOOL as *const std::ffi::c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+
+    success.as_bool() && !animations_enabled.as_bool()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn system_prefers_reduced_motion() -> bool {
+    false
+}
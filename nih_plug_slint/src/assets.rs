@@ -0,0 +1,185 @@
+//! Fonts and images shipped alongside the plugin.
+
+use crate::logging::warn_log;
+use std::path::{Path, PathBuf};
+
+/// The file extensions of the font files that are registered from an asset directory.
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+/// A directory with fonts and images the plugin ships with, for skinned GUIs that use more assets
+/// than are practical to embed or list one by one. Set it as
+/// [`SlintEditorOptions::assets`][crate::SlintEditorOptions::assets] to have every font file in
+/// the directory registered before the component factory runs, and keep a clone around in the
+/// factory to load images from it with [`image()`][Self::image()].
+///
+/// Only files directly inside of the directory are registered. Subdirectories are not searched.
+/// Registering fonts at runtime requires the `fonts` feature, since Slint's software renderer
+/// otherwise only draws fonts that were embedded at compile time. Fonts are shared by the entire
+/// process, so each font file is only registered once no matter how many plugin instances open
+/// their editors. A missing or unreadable directory is logged and otherwise ignored, so the
+/// component still gets created and falls back to the default font.
+///
+/// # Example
+///
+/// ```ignore
+/// let assets = SlintAssetDirectory::new(bundle_resources_dir.join("skin"));
+///
+/// create_slint_editor_with_options(
+///     self.params.editor_state.clone(),
+///     {
+///         let assets = assets.clone();
+///         move |gui_context, mouse_control| {
+///             let ui = MyPluginUI::new().unwrap();
+///             ui.set_background(assets.image("background.png"));
+///             ui
+///         }
+///     },
+///     None,
+///     SlintEditorOptions {
+///         assets: Some(assets),
+///         ..Default::default()
+///     },
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlintAssetDirectory {
+    root: PathBuf,
+}
+
+impl SlintAssetDirectory {
+    /// Use the assets in `root`. The directory is only read when the editor opens, so it doesn't
+    /// need to exist yet.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The directory's path.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The path of the asset called `name`, relative to the directory.
+    pub fn path(&self, name: impl AsRef<Path>) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// Load the image called `name` from the directory. If the image can't be loaded, a warning
+    /// is logged and an empty image is returned so a missing asset doesn't break the entire GUI.
+    /// Which formats can be decoded depends on the image features enabled for Slint. Large images
+    /// can be loaded with [`load_image_in_background()`][crate::load_image_in_background()]
+    /// instead so they don't delay opening the editor.
+    pub fn image(&self, name: impl AsRef<Path>) -> slint::Image {
+        let path = self.path(name);
+        slint::Image::load_from_path(&path).unwrap_or_else(|err| {
+            warn_log!("Could not load the image '{}': {:?}", path.display(), err);
+            slint::Image::default()
+        })
+    }
+
+    /// Register all fonts in the directory that haven't been registered yet. Must be called after
+    /// the Slint platform has been set up.
+    pub(crate) fn register_fonts(&self) {
+        let font_files = font_files_in(&self.root);
+        if font_files.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "fonts")]
+        {
+            use crate::logging::debug_log;
+            use parking_lot::Mutex;
+            use std::collections::HashSet;
+            use std::sync::OnceLock;
+
+            // Fonts are registered with Slint's process-wide font collection, so this is tracked
+            // per process rather than per editor
+            static REGISTERED_FONTS: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+            let mut registered_fonts = REGISTERED_FONTS.get_or_init(Default::default).lock();
+
+            let mut collection = slint::fontique_011::shared_collection();
+            for path in font_files {
+                let path = path.canonicalize().unwrap_or(path);
+                if registered_fonts.contains(&path) {
+                    continue;
+                }
+
+                match std::fs::read(&path) {
+                    Ok(data) => {
+                        let blob =
+                            slint::fontique_011::fontique::Blob::new(std::sync::Arc::new(data));
+                        collection.register_fonts(blob, None);
+                        debug_log!("Registered the font '{}'", path.display());
+                        registered_fonts.insert(path);
+                    }
+                    Err(err) => warn_log!("Could not read the font '{}': {}", path.display(), err),
+                }
+            }
+        }
+
+        #[cfg(not(feature = "fonts"))]
+        warn_log!(
+            "Not registering the {} fonts in '{}' because the 'fonts' feature is disabled",
+            font_files.len(),
+            self.root.display()
+        );
+    }
+}
+
+/// The font files directly inside of `directory`, sorted by path. Returns an empty list if the
+/// directory can't be read.
+fn font_files_in(directory: &Path) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn_log!(
+                "Could not read the asset directory '{}': {}",
+                directory.display(),
+                err
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut font_files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_font_file(path))
+        .collect();
+    font_files.sort();
+
+    font_files
+}
+
+/// Whether `path` has one of the [`FONT_EXTENSIONS`], ignoring case.
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| {
+            FONT_EXTENSIONS
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_font_files_are_found() {
+        let directory =
+            std::env::temp_dir().join(format!("nih_plug_slint_assets_test_{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("nested.ttf")).unwrap();
+        for name in ["b.otf", "a.TTF", "background.png", "readme"] {
+            std::fs::write(directory.join(name), b"").unwrap();
+        }
+
+        assert_eq!(
+            font_files_in(&directory),
+            vec![directory.join("a.TTF"), directory.join("b.otf")]
+        );
+        assert!(font_files_in(&directory.join("missing")).is_empty());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
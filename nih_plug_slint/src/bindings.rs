@@ -0,0 +1,179 @@
+//! Compile-time checked parameter bindings.
+
+use std::cell::Cell;
+
+thread_local! {
+    /// Whether the GUI thread is currently applying parameter values from the host to the
+    /// component. See [`sync_from_host()`].
+    static SYNCING_FROM_HOST: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` while marking the current thread as applying host-driven parameter changes to the
+/// component. The parameter changed callback passed to
+/// [`create_slint_editor_with_param_callback()`][crate::create_slint_editor_with_param_callback()]
+/// and the `sync` function generated by [`param_bindings!`] already run inside of this.
+///
+/// Setting a Slint property can cause the component's own change callbacks to fire, which in a
+/// two-way binding would send the value right back to the host as a new parameter change. While
+/// this is active, [`SlintParamContext::set_normalized()`][crate::SlintParamContext::set_normalized()]
+/// and the other setters on [`SlintParamContext`][crate::SlintParamContext] ignore changes, so
+/// those echoes never reach the host. Hand-written callbacks that use a
+/// [`ParamSetter`][nih_plug::prelude::ParamSetter] directly should check
+/// [`is_syncing_from_host()`] themselves.
+pub fn sync_from_host<R>(f: impl FnOnce() -> R) -> R {
+    /// Restores the previous state even if `f` panics.
+    struct Guard(bool);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SYNCING_FROM_HOST.with(|syncing| syncing.set(self.0));
+        }
+    }
+
+    let _guard = Guard(SYNCING_FROM_HOST.with(|syncing| syncing.replace(true)));
+    f()
+}
+
+/// Whether the current thread is applying host-driven parameter changes to the component. See
+/// [`sync_from_host()`].
+pub fn is_syncing_from_host() -> bool {
+    SYNCING_FROM_HOST.with(|syncing| syncing.get())
+}
+
+/// Generate the functions needed to bind a plugin's parameters to a Slint component. This
+/// replaces the hand written `set_*()` and `on_*_changed()` wiring in the component factory and
+/// the parameter changed callback. Every parameter field, property setter, and callback name is
+/// a plain identifier, so a typo or a renamed `.slint` property results in a compile error instead
+/// of a silently unbound control.
+///
+/// The bound Slint properties and callbacks use the parameters' normalized `[0, 1]` values. Use
+/// [`SlintParamContext::to_plain()`][crate::SlintParamContext::to_plain()] or the parameter's
+/// formatting functions for display purposes. The macro generates two functions:
+///
+/// - `bind(ui: &Component, params: &Arc<Params>, gui_context: &Arc<dyn GuiContext>)` sets all
+///   properties to the parameters' current values and connects all change callbacks. Call this
+///   from the component factory.
+/// - `sync(ui: &Component, params: &Params)` sets all properties to the parameters' current
+///   values. Call this from the parameter changed callback.
+///
+/// Property changes made by `sync` are not sent back to the host, even if they trigger the bound
+/// change callbacks. See [`sync_from_host()`].
+///
+/// A binding with only a change callback sets the parameter as a complete gesture for every
+/// value. That's fine for controls that change in discrete steps, but a dragged slider would
+/// record hundreds of separate gestures, which breaks touch and latch automation in most hosts.
+/// For controls that are dragged, also list callbacks for pressing and releasing the control.
+/// Pressing then starts a gesture, the values sent while dragging become part of it, and
+/// releasing ends it. The press and release callbacks don't take any arguments.
+///
+/// # Example
+///
+/// ```ignore
+/// // In the .slint file:
+/// // export component PluginUI inherits Window {
+/// //     in property <float> gain;
+/// //     callback gain-changed(float);
+/// //     callback gain-pressed();
+/// //     callback gain-released();
+/// //     ...
+/// // }
+///
+/// nih_plug_slint::param_bindings! {
+///     component: PluginUI,
+///     params: GainParams,
+///     bind: bind_params,
+///     sync: sync_params,
+///     bindings: {
+///         gain => set_gain, on_gain_changed, on_gain_pressed, on_gain_released;
+///         mode => set_mode, on_mode_changed;
+///     }
+/// }
+///
+/// fn editor(&mut self, _: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
+///     let params = self.params.clone();
+///     let sync_params_clone = self.params.clone();
+///
+///     create_slint_editor_with_param_callback(
+///         self.params.editor_state.clone(),
+///         move |gui_context, _mouse_control| {
+///             let ui = PluginUI::new().unwrap();
+///             bind_params(&ui, &params, &gui_context);
+///             ui
+///         },
+///         Some(Arc::new(move |ui: &PluginUI| sync_params(ui, &sync_params_clone))),
+///     )
+/// }
+/// ```
+#[macro_export]
+macro_rules! param_bindings {
+    (
+        component: $component:ty,
+        params: $params:ty,
+        bind: $bind_fn:ident,
+        sync: $sync_fn:ident,
+        bindings: {
+            $(
+                $field:ident => $setter:ident, $on_changed:ident
+                $(, $on_pressed:ident, $on_released:ident)?;
+            )*
+        } $(,)?
+    ) => {
+        fn $bind_fn(
+            ui: &$component,
+            params: &::std::sync::Arc<$params>,
+            gui_context: &::std::sync::Arc<dyn ::nih_plug::prelude::GuiContext>,
+        ) {
+            $sync_fn(ui, params);
+
+            let param_context = $crate::SlintParamContext::new(::std::sync::Arc::clone(gui_context));
+            $(
+                // Only set for bindings with press and release callbacks
+                let in_gesture =
+                    ::std::sync::Arc::new(::std::sync::atomic::AtomicBool::new(false));
+                $(
+                    ui.$on_pressed({
+                        let param_context = param_context.clone();
+                        let params = ::std::sync::Arc::clone(params);
+                        let in_gesture = ::std::sync::Arc::clone(&in_gesture);
+                        move || {
+                            if !in_gesture.swap(true, ::std::sync::atomic::Ordering::AcqRel) {
+                                param_context.begin_gesture(&params.$field);
+                            }
+                        }
+                    });
+                    ui.$on_released({
+                        let param_context = param_context.clone();
+                        let params = ::std::sync::Arc::clone(params);
+                        let in_gesture = ::std::sync::Arc::clone(&in_gesture);
+                        move || {
+                            if in_gesture.swap(false, ::std::sync::atomic::Ordering::AcqRel) {
+                                param_context.end_gesture(&params.$field);
+                            }
+                        }
+                    });
+                )?
+                ui.$on_changed({
+                    let param_context = param_context.clone();
+                    let params = ::std::sync::Arc::clone(params);
+                    move |normalized| {
+                        if in_gesture.load(::std::sync::atomic::Ordering::Acquire) {
+                            param_context.set_in_gesture(&params.$field, normalized);
+                        } else {
+                            param_context.set_normalized(&params.$field, normalized);
+                        }
+                    }
+                });
+            )*
+        }
+
+        fn $sync_fn(ui: &$component, params: &$params) {
+            $crate::sync_from_host(|| {
+                $(
+                    ui.$setter(::nih_plug::prelude::Param::unmodulated_normalized_value(
+                        &params.$field,
+                    ));
+                )*
+            });
+        }
+    };
+}
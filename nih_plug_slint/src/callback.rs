@@ -0,0 +1,69 @@
+//! Optional callbacks that can be set from any thread and are called from the GUI thread.
+
+use parking_lot::Mutex;
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::SlintComponent;
+
+/// An optional callback of type `F`, which is usually a `dyn Fn(...) + Send + Sync`.
+pub(crate) struct Callback<F: ?Sized>(Mutex<Option<Arc<F>>>);
+
+/// A callback that receives the editor's Slint component. The component is passed as a `&dyn Any`
+/// since neither [`SlintState`][crate::SlintState] nor the focus ring is generic over the
+/// component type. See [`set_for()`][Callback::set_for()].
+pub(crate) type ComponentCallback<T> = Callback<dyn Fn(&dyn Any, T) + Send + Sync>;
+
+impl<F: ?Sized> Default for Callback<F> {
+    fn default() -> Self {
+        Self(Mutex::new(None))
+    }
+}
+
+impl<F: ?Sized> std::fmt::Debug for Callback<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Callback")
+            .field(&self.0.lock().is_some())
+            .finish()
+    }
+}
+
+impl<F: ?Sized> Callback<F> {
+    /// Replace the callback.
+    pub fn set(&self, callback: Arc<F>) {
+        *self.0.lock() = Some(callback);
+    }
+
+    /// Remove the callback.
+    pub fn clear(&self) {
+        *self.0.lock() = None;
+    }
+
+    /// The current callback, if any. This is cloned out of the lock so the callback can safely
+    /// replace itself while it runs.
+    pub fn get(&self) -> Option<Arc<F>> {
+        self.0.lock().clone()
+    }
+}
+
+impl<T: 'static> ComponentCallback<T> {
+    /// Replace the callback with `f`, which is only called if the component is a `C`.
+    pub fn set_for<C, F>(&self, f: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, T) + Send + Sync + 'static,
+    {
+        self.set(Arc::new(move |component: &dyn Any, value| {
+            if let Some(component) = component.downcast_ref::<C>() {
+                f(component, value);
+            }
+        }));
+    }
+
+    /// Call the callback, if any, with the component and `value`.
+    pub fn call(&self, component: &dyn Any, value: T) {
+        if let Some(callback) = self.get() {
+            callback(component, value);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Checking the system clipboard's contents and copying text to it. Baseview can only write to
+//! the clipboard on macOS, so this talks to the platform's clipboard directly elsewhere.
+
+/// The standard Windows clipboard format for UTF-16 text. Windows converts the other text formats
+/// to this one on demand.
+#[cfg(target_os = "windows")]
+const CF_UNICODETEXT: u32 = 13;
+
+/// Whether the system clipboard currently holds text. Always returns `false` on platforms where
+/// the clipboard can't be read, which currently includes Linux.
+#[cfg(target_os = "macos")]
+pub(crate) fn clipboard_has_text() -> bool {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        static NSPasteboardTypeString: *mut Object;
+    }
+
+    unsafe {
+        let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+        if pasteboard.is_null() {
+            return false;
+        }
+
+        let types: *mut Object = msg_send![
+            class!(NSArray),
+            arrayWithObject: NSPasteboardTypeString
+        ];
+        let available: *mut Object = msg_send![pasteboard, availableTypeFromArray: types];
+        !available.is_null()
+    }
+}
+
+/// Whether the system clipboard currently holds text. Always returns `false` on platforms where
+/// the clipboard can't be read, which currently includes Linux.
+#[cfg(target_os = "windows")]
+pub(crate) fn clipboard_has_text() -> bool {
+    use windows::Win32::System::DataExchange::IsClipboardFormatAvailable;
+
+    unsafe { IsClipboardFormatAvailable(CF_UNICODETEXT).as_bool() }
+}
+
+/// Whether the system clipboard currently holds text. Always returns `false` on platforms where
+/// the clipboard can't be read, which currently includes Linux.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn clipboard_has_text() -> bool {
+    false
+}
+
+/// Replace the system clipboard's contents with `text`. Returns `false` if that failed or if the
+/// clipboard can't be written to on this platform, which currently includes Linux.
+#[cfg(target_os = "macos")]
+pub(crate) fn copy_text_to_clipboard(text: &str) -> bool {
+    baseview::copy_to_clipboard(text);
+    true
+}
+
+/// Replace the system clipboard's contents with `text`. Returns `false` if that failed or if the
+/// clipboard can't be written to on this platform, which currently includes Linux.
+#[cfg(target_os = "windows")]
+pub(crate) fn copy_text_to_clipboard(text: &str) -> bool {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::DataExchange::{CloseClipboard, OpenClipboard};
+
+    let text: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    unsafe {
+        // The clipboard is opened for the current thread, since the editor's window isn't known
+        // here
+        if !OpenClipboard(HWND(0)).as_bool() {
+            return false;
+        }
+
+        let copied = set_clipboard_text(&text);
+        let _ = CloseClipboard();
+        copied
+    }
+}
+
+/// Store the null-terminated UTF-16 `text` on the clipboard, which must be open.
+#[cfg(target_os = "windows")]
+unsafe fn set_clipboard_text(text: &[u16]) -> bool {
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::DataExchange::{EmptyClipboard, SetClipboardData};
+    use windows::Win32::System::Memory::{
+        GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE,
+    };
+
+    if !EmptyClipboard().as_bool() {
+        return false;
+    }
+
+    let memory = GlobalAlloc(GMEM_MOVEABLE, std::mem::size_of_val(text));
+    if memory == 0 {
+        return false;
+    }
+    let target = GlobalLock(memory) as *mut u16;
+    if target.is_null() {
+        GlobalFree(memory);
+        return false;
+    }
+    std::ptr::copy_nonoverlapping(text.as_ptr(), target, text.len());
+    let _ = GlobalUnlock(memory);
+
+    // The clipboard takes ownership of the memory only if this succeeds
+    if SetClipboardData(CF_UNICODETEXT, HANDLE(memory)).is_err() {
+        GlobalFree(memory);
+        return false;
+    }
+
+    true
+}
+
+/// Replace the system clipboard's contents with `text`. Returns `false` if that failed or if the
+/// clipboard can't be written to on this platform, which currently includes Linux.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn copy_text_to_clipboard(_text: &str) -> bool {
+    false
+}
@@ -0,0 +1,82 @@
+//! The interface between the editor and the component it shows.
+
+/// A component that can be shown in the editor's window. This is implemented for every Slint
+/// component, so most plugins never need to deal with it directly.
+///
+/// Implementing this for your own type lets a single editor switch between entirely different
+/// Slint components at runtime, for instance between a simple and an advanced view defined in
+/// separate `.slint` files. Wrap the components in an enum, implement this trait for it by
+/// forwarding to the active variant, and have the component factory create the variant for the
+/// currently selected view. [`SlintState::request_rebuild()`][crate::SlintState::request_rebuild()]
+/// then switches views by recreating the component within the same window.
+///
+/// There are a couple of constraints to keep in mind:
+///
+/// - Only one view exists at a time. Switching drops the old component, so any state that should
+///   survive the switch needs to live outside of it, like in the plugin's parameters or persisted
+///   fields.
+/// - The views share the editor's window, and with that its size. Use
+///   [`SlintState::request_resize()`][crate::SlintState::request_resize()] or
+///   [`SlintState::fit_to_content()`][crate::SlintState::fit_to_content()] from the factory if
+///   the views need different sizes.
+/// - Callbacks that receive the component, like the parameter change callback and
+///   [`SlintState::update_component()`][crate::SlintState::update_component()], receive the enum
+///   and need to match on it.
+/// - The switch happens on the GUI thread's next frame, not immediately.
+///
+/// Both this trait and [`slint::ComponentHandle`] have `show()` and `hide()` methods, so calls
+/// need to name the trait when both are in scope.
+///
+/// # Example
+///
+/// ```ignore
+/// use nih_plug_slint::slint::ComponentHandle;
+///
+/// enum MyPluginUI {
+///     Simple(SimpleUI),
+///     Advanced(AdvancedUI),
+/// }
+///
+/// impl SlintComponent for MyPluginUI {
+///     fn show(&self) -> Result<(), slint::PlatformError> {
+///         match self {
+///             MyPluginUI::Simple(ui) => ComponentHandle::show(ui),
+///             MyPluginUI::Advanced(ui) => ComponentHandle::show(ui),
+///         }
+///     }
+///
+///     fn hide(&self) -> Result<(), slint::PlatformError> {
+///         match self {
+///             MyPluginUI::Simple(ui) => ComponentHandle::hide(ui),
+///             MyPluginUI::Advanced(ui) => ComponentHandle::hide(ui),
+///         }
+///     }
+/// }
+///
+/// // In the component factory, with `advanced_view: Arc<AtomicBool>` shared with the callbacks
+/// // that switch views and then call `editor_state.request_rebuild()`:
+/// if advanced_view.load(Ordering::Relaxed) {
+///     MyPluginUI::Advanced(AdvancedUI::new().unwrap())
+/// } else {
+///     MyPluginUI::Simple(SimpleUI::new().unwrap())
+/// }
+/// ```
+pub trait SlintComponent: 'static {
+    /// Show the component in the editor's window. This is called right after the component
+    /// factory returns.
+    fn show(&self) -> Result<(), slint::PlatformError>;
+
+    /// Hide the component. This is called before the component is dropped, either because the
+    /// editor closes or because the component is rebuilt.
+    fn hide(&self) -> Result<(), slint::PlatformError>;
+}
+
+impl<C: slint::ComponentHandle + 'static> SlintComponent for C {
+    fn show(&self) -> Result<(), slint::PlatformError> {
+        slint::ComponentHandle::show(self)
+    }
+
+    fn hide(&self) -> Result<(), slint::PlatformError> {
+        slint::ComponentHandle::hide(self)
+    }
+}
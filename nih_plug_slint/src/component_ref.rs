@@ -0,0 +1,112 @@
+//! A weak reference to the editor's Slint component.
+
+use crate::logging::trace_log;
+use crate::thread_check::ThreadAffinity;
+
+/// A weak reference to a Slint component for use in callbacks, timers, and other deferred
+/// updates. Slint callbacks must not capture a strong component handle since that creates a
+/// reference cycle, and the component may have been dropped by the time a deferred update runs
+/// because the editor was closed or rebuilt. This wraps [`slint::Weak`] so the upgrade and the
+/// component-is-gone case are handled in one place.
+///
+/// Like [`slint::Weak`], the component can only be accessed from the GUI thread that created it.
+/// To update the component from another thread, for instance the audio thread, use
+/// [`SlintState::update_component()`][crate::SlintState::update_component()] instead. With the
+/// `thread-checks` feature enabled, using the reference from the wrong thread panics in debug
+/// builds instead of silently skipping the update.
+///
+/// # Example
+///
+/// ```ignore
+/// // In the component factory:
+/// let ui_ref = SlintComponentRef::new(&ui);
+/// let timer = slint::Timer::default();
+/// timer.start(slint::TimerMode::Repeated, Duration::from_millis(16), move || {
+///     ui_ref.with_component(|ui| ui.set_peak_level(peak_meter.next()));
+/// });
+/// ```
+pub struct SlintComponentRef<C: slint::ComponentHandle> {
+    weak: slint::Weak<C>,
+    affinity: ThreadAffinity,
+}
+
+impl<C: slint::ComponentHandle> SlintComponentRef<C> {
+    /// Create a weak reference to `component`.
+    pub fn new(component: &C) -> Self {
+        Self {
+            weak: component.as_weak(),
+            affinity: ThreadAffinity::current(),
+        }
+    }
+
+    /// Run `f` with the component and return its result. Returns `None` without running `f` if
+    /// the component has been dropped or if this is called from a thread other than the GUI
+    /// thread.
+    pub fn with_component<R>(&self, f: impl FnOnce(&C) -> R) -> Option<R> {
+        self.affinity.check("SlintComponentRef");
+        match self.weak.upgrade() {
+            Some(component) => Some(f(&component)),
+            None => {
+                trace_log!("Component is no longer alive, skipping the update");
+                None
+            }
+        }
+    }
+
+    /// Whether the component is still alive and accessible from the current thread.
+    pub fn is_alive(&self) -> bool {
+        self.affinity.check("SlintComponentRef");
+        self.weak.upgrade().is_some()
+    }
+
+    /// Get the underlying [`slint::Weak`], for APIs that need one directly.
+    pub fn as_weak(&self) -> &slint::Weak<C> {
+        &self.weak
+    }
+}
+
+impl<C: slint::ComponentHandle> Clone for SlintComponentRef<C> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: self.weak.clone(),
+            affinity: self.affinity,
+        }
+    }
+}
+
+impl<C: slint::ComponentHandle> From<slint::Weak<C>> for SlintComponentRef<C> {
+    fn from(weak: slint::Weak<C>) -> Self {
+        Self {
+            weak,
+            // A weak reference can be sent to other threads, so this may not be the GUI thread
+            affinity: ThreadAffinity::unknown(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_window::show_in_test_window;
+    use slint::ComponentHandle;
+
+    slint::slint! {
+        export component RefTest inherits Window {
+            in-out property <int> value;
+        }
+    }
+
+    #[test]
+    fn dropped_component_is_skipped() {
+        let (_window, ui) = show_in_test_window(|| RefTest::new().unwrap());
+        let ui_ref = SlintComponentRef::new(&ui);
+
+        assert_eq!(ui_ref.with_component(|ui| ui.set_value(1)), Some(()));
+        assert_eq!(ui.get_value(), 1);
+
+        ui.hide().unwrap();
+        drop(ui);
+        assert!(!ui_ref.is_alive());
+        assert_eq!(ui_ref.with_component(|ui| ui.set_value(2)), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Helpers for mapping pointer positions in custom-drawn controls to parameter values.
+//!
+//! Custom sliders, knobs, and pads drawn in Slint usually report the pointer's position from a
+//! `TouchArea` and leave it to Rust to turn that into a parameter value. Use
+//! [`normalized_from_position()`] for the conversion and set the result through
+//! [`SlintParamContext`][crate::SlintParamContext]:
+//!
+//! ```ignore
+//! // In the .slint file, the control calls `root.cutoff-moved(self.absolute-position.x +
+//! // self.mouse-x, self.absolute-position.y + self.mouse-y)` from its `TouchArea`'s `moved`
+//! // handler.
+//!
+//! // In the component factory:
+//! ui.on_cutoff_moved({
+//!     let param_context = param_context.clone();
+//!     let params = params.clone();
+//!     move |x, y| {
+//!         let normalized = nih_plug_slint::normalized_from_position(
+//!             slint::LogicalPosition::new(x, y),
+//!             slint::LogicalPosition::new(20.0, 40.0),
+//!             slint::LogicalSize::new(200.0, 24.0),
+//!             nih_plug_slint::ControlAxis::Horizontal,
+//!         );
+//!         param_context.set_normalized(&params.cutoff, normalized);
+//!     }
+//! });
+//! ```
+
+use slint::{LogicalPosition, LogicalSize};
+
+/// How a control's area maps to a normalized `[0, 1]` value. See [`normalized_from_position()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlAxis {
+    /// The left edge is 0 and the right edge is 1.
+    Horizontal,
+    /// The bottom edge is 0 and the top edge is 1, like a fader.
+    Vertical,
+    /// The angle around the control's center, like a knob. Angles are in degrees, with 0 pointing
+    /// up and positive angles going clockwise, which matches Slint's `rotation-angle`. The value
+    /// is 0 at `start_angle` and 1 at `start_angle + sweep`. Positions in the gap outside of the
+    /// sweep snap to the closer end. A typical knob uses a `start_angle` of -135 and a `sweep` of
+    /// 270.
+    Radial { start_angle: f32, sweep: f32 },
+}
+
+/// Convert a pointer `position` to a normalized `[0, 1]` value for a control whose top-left corner
+/// is at `control_origin` and that is `control_size` large, along `axis`. All coordinates are in
+/// logical pixels and need to be relative to the same origin, usually the window. Positions
+/// outside of the control are clamped to its edges, and a control without an area maps
+/// everything to 0.
+pub fn normalized_from_position(
+    position: LogicalPosition,
+    control_origin: LogicalPosition,
+    control_size: LogicalSize,
+    axis: ControlAxis,
+) -> f32 {
+    let x = position.x - control_origin.x;
+    let y = position.y - control_origin.y;
+
+    match axis {
+        ControlAxis::Horizontal => fraction(x, control_size.width),
+        ControlAxis::Vertical if control_size.height > 0.0 => {
+            1.0 - fraction(y, control_size.height)
+        }
+        ControlAxis::Vertical => 0.0,
+        ControlAxis::Radial { start_angle, sweep } => {
+            if sweep <= 0.0 {
+                return 0.0;
+            }
+
+            // Zero points up and positive angles go clockwise since the y axis points down
+            let dx = x - control_size.width / 2.0;
+            let dy = y - control_size.height / 2.0;
+            let angle = dx.atan2(-dy).to_degrees();
+
+            let relative = (angle - start_angle).rem_euclid(360.0);
+            if relative <= sweep {
+                relative / sweep
+            } else if relative - sweep < 360.0 - relative {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+}
+
+/// `offset / length` clamped to `[0, 1]`, or 0 if `length` is not positive.
+fn fraction(offset: f32, length: f32) -> f32 {
+    if length > 0.0 {
+        (offset / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_map_to_normalized_values() {
+        let origin = LogicalPosition::new(10.0, 10.0);
+        let size = LogicalSize::new(100.0, 100.0);
+        let map =
+            |x, y, axis| normalized_from_position(LogicalPosition::new(x, y), origin, size, axis);
+
+        assert_eq!(map(35.0, 0.0, ControlAxis::Horizontal), 0.25);
+        assert_eq!(map(500.0, 0.0, ControlAxis::Horizontal), 1.0);
+        assert_eq!(map(0.0, 35.0, ControlAxis::Vertical), 0.75);
+
+        let knob = ControlAxis::Radial {
+            start_angle: -135.0,
+            sweep: 270.0,
+        };
+        // Straight up is the middle, straight right is 90 degrees past that
+        assert_eq!(map(60.0, 0.0, knob), 0.5);
+        assert!((map(110.0, 60.0, knob) - 225.0 / 270.0).abs() < 1e-5);
+        // The gap at the bottom snaps to the closer end
+        assert_eq!(map(50.0, 110.0, knob), 0.0);
+        assert_eq!(map(70.0, 110.0, knob), 1.0);
+    }
+}
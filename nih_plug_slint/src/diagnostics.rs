@@ -0,0 +1,139 @@
+//! A summary of the editor's state for bug reports.
+
+use slint::platform::software_renderer::RepaintBufferType;
+use std::fmt;
+
+use crate::{SlintEditorError, SlintScaleInfo};
+
+/// A snapshot of everything relevant for troubleshooting sizing, scaling, and rendering issues.
+/// See [`SlintState::diagnostics()`][crate::SlintState::diagnostics()]. The [`Display`][fmt::Display]
+/// implementation formats it as a few lines of plain text that can be pasted into a bug report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlintDiagnostics {
+    /// Whether the editor is open.
+    pub open: bool,
+    /// Whether the editor is [suspended][crate::SlintState::suspend()].
+    pub suspended: bool,
+    /// Whether the editor's window has keyboard focus, as last reported by the window system.
+    pub focused: bool,
+    /// The editor's logical size as reported to the host. See
+    /// [`SlintState::size()`][crate::SlintState::size()].
+    pub size: (u32, u32),
+    /// See [`SlintState::user_scale_factor()`][crate::SlintState::user_scale_factor()].
+    pub user_scale_factor: f64,
+    /// See [`SlintState::content_scale()`][crate::SlintState::content_scale()].
+    pub content_scale: f64,
+    /// The open editor's scale factors and sizes, or `None` if the editor is not open.
+    pub scale_info: Option<SlintScaleInfo>,
+    /// How the open editor renders, or `None` if the editor is not open.
+    pub render_mode: Option<SlintRenderMode>,
+    /// See [`SlintState::last_error()`][crate::SlintState::last_error()].
+    pub last_error: Option<SlintEditorError>,
+}
+
+/// How the open editor renders its frames. Part of [`SlintDiagnostics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlintRenderMode {
+    /// The repaint buffer type Slint's renderer uses. See
+    /// [`SlintEditorOptions::repaint_buffer`][crate::SlintEditorOptions::repaint_buffer].
+    pub repaint_buffer: RepaintBufferType,
+    /// See [`SlintEditorOptions::redraw_on_demand`][crate::SlintEditorOptions::redraw_on_demand].
+    pub redraw_on_demand: bool,
+    /// See [`SlintEditorOptions::static_content`][crate::SlintEditorOptions::static_content].
+    pub static_content: bool,
+    /// Whether frames go to a [`SlintRenderTarget`][crate::SlintRenderTarget] instead of the
+    /// window's softbuffer surface.
+    pub custom_render_target: bool,
+}
+
+impl fmt::Display for SlintDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+        writeln!(f, "nih_plug_slint {}", env!("CARGO_PKG_VERSION"))?;
+        writeln!(
+            f,
+            "open: {}, suspended: {}, focused: {}",
+            yes_no(self.open),
+            yes_no(self.suspended),
+            yes_no(self.focused)
+        )?;
+        writeln!(
+            f,
+            "size: {}x{}, user scale: {}, content scale: {}",
+            self.size.0, self.size.1, self.user_scale_factor, self.content_scale
+        )?;
+
+        match &self.scale_info {
+            Some(scale_info) => {
+                writeln!(
+                    f,
+                    "scale factor: {} ({:?}), render scale: {}, Slint scale factor: {}",
+                    scale_info.scale_factor,
+                    scale_info.scale_source,
+                    scale_info.render_scale,
+                    scale_info.slint_scale_factor
+                )?;
+                writeln!(
+                    f,
+                    "window: {}x{} logical, {}x{} physical, {}x{} rendered",
+                    scale_info.logical_size.0,
+                    scale_info.logical_size.1,
+                    scale_info.physical_size.0,
+                    scale_info.physical_size.1,
+                    scale_info.render_size.0,
+                    scale_info.render_size.1
+                )?;
+            }
+            None => writeln!(f, "window: not open")?,
+        }
+
+        if let Some(render_mode) = &self.render_mode {
+            writeln!(
+                f,
+                "rendering: {:?}, on demand: {}, static: {}, custom target: {}",
+                render_mode.repaint_buffer,
+                yes_no(render_mode.redraw_on_demand),
+                yes_no(render_mode.static_content),
+                yes_no(render_mode.custom_render_target)
+            )?;
+        }
+
+        match &self.last_error {
+            Some(error) => write!(f, "last error: {error}"),
+            None => write!(f, "last error: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_editor_diagnostics() {
+        let diagnostics = SlintDiagnostics {
+            open: false,
+            suspended: false,
+            focused: false,
+            size: (800, 600),
+            user_scale_factor: 1.5,
+            content_scale: 1.0,
+            scale_info: None,
+            render_mode: None,
+            last_error: Some(SlintEditorError::SoftbufferSurface("no surface".into())),
+        };
+
+        let text = diagnostics.to_string();
+        let lines: Vec<&str> = text.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            [
+                "open: no, suspended: no, focused: no",
+                "size: 800x600, user scale: 1.5, content scale: 1",
+                "window: not open",
+                "last error: could not create the softbuffer surface: no surface",
+            ]
+        );
+    }
+}
@@ -1,13 +1,19 @@
 //! An [`Editor`] implementation for Slint.
 
+use crate::logging::{trace_log, warn_log};
+use crate::parent_scale::parent_scale_factor;
 use crate::platform::ensure_slint_platform;
+use crate::render_target::SlintRenderTarget;
 use crate::window_handler::SlintWindowHandler;
-use crate::{SlintMouseControl, SlintState};
+use crate::{
+    ScalePolicy, SlintComponent, SlintEditorOptions, SlintMouseControl, SlintScaleSource,
+    SlintState,
+};
 use baseview::{Size, WindowHandle, WindowOpenOptions, WindowScalePolicy};
 use crossbeam::atomic::AtomicCell;
 use nih_plug::prelude::{Editor, GuiContext, ParentWindowHandle};
 use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Type alias for the param values changed callback.
@@ -16,11 +22,15 @@ pub type ParamChangedCallback<C> = Arc<dyn Fn(&C) + Send + Sync>;
 /// An [`Editor`] implementation that uses Slint for rendering.
 pub(crate) struct SlintEditor<C, F>
 where
-    C: slint::ComponentHandle + 'static,
+    C: SlintComponent,
     F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
 {
     pub(crate) slint_state: Arc<SlintState>,
     pub(crate) component_factory: Arc<F>,
+    /// Additional options for the editor and its window handler.
+    pub(crate) options: SlintEditorOptions,
+    /// Receives the rendered frames instead of the window's softbuffer surface, if set.
+    pub(crate) render_target: Option<Arc<dyn SlintRenderTarget>>,
     /// The scaling factor reported by the host, if any. On macOS this will never be set and we
     /// should use the system scaling factor instead.
     pub(crate) scaling_factor: AtomicCell<Option<f32>>,
@@ -29,10 +39,58 @@ where
     /// Whether to invoke the param changed callback during the next frame. This is set in the
     /// `param_values_changed()` implementation and checked by the window handler in `on_frame`.
     pub(crate) emit_parameters_changed_event: Arc<AtomicBool>,
+    /// Tells the windows spawned by this editor apart, for hosts that spawn it again without
+    /// dropping the previous handle first.
+    pub(crate) spawned_windows: Arc<SpawnedWindows>,
+}
+
+/// Tracks which of an editor's windows is the current one. Some hosts call [`Editor::spawn()`]
+/// again without dropping the previous handle, for instance while docking the editor. The most
+/// recently spawned window then replaces the earlier ones, which close themselves on their next
+/// frame.
+#[derive(Debug, Default)]
+pub(crate) struct SpawnedWindows {
+    /// The generation of the most recently spawned window.
+    latest: AtomicU64,
+}
+
+impl SpawnedWindows {
+    /// Register a newly spawned window, which makes all earlier windows stale.
+    pub(crate) fn register(self: &Arc<Self>) -> SpawnToken {
+        SpawnToken {
+            windows: Arc::clone(self),
+            generation: self.latest.fetch_add(1, Ordering::AcqRel) + 1,
+        }
+    }
+}
+
+/// Identifies one window spawned by the editor. See [`SpawnedWindows`].
+#[derive(Debug, Clone)]
+pub(crate) struct SpawnToken {
+    windows: Arc<SpawnedWindows>,
+    generation: u64,
+}
+
+impl SpawnToken {
+    /// Whether no other window has been spawned since this one.
+    pub(crate) fn is_current(&self) -> bool {
+        self.windows.latest.load(Ordering::Acquire) == self.generation
+    }
+
+    /// Mark the editor as closed when this window's handle is dropped, unless a newer window has
+    /// replaced it in the meantime and is still open.
+    fn release(&self, slint_state: &SlintState) {
+        if self.is_current() {
+            slint_state.set_open(false);
+            slint_state.clear_component_updates();
+        }
+    }
 }
 
 /// This version of `baseview` uses a different version of `raw_window_handle` than NIH-plug, so we
-/// need to adapt it ourselves.
+/// need to adapt it ourselves. NIH-plug's parent handles don't have a Wayland variant yet, since
+/// no plugin API currently passes Wayland surfaces to plugins. Wayland child windows created by
+/// baseview are handled when setting up the softbuffer surface.
 struct ParentWindowHandleAdapter(nih_plug::editor::ParentWindowHandle);
 
 unsafe impl HasRawWindowHandle for ParentWindowHandleAdapter {
@@ -59,7 +117,7 @@ unsafe impl HasRawWindowHandle for ParentWindowHandleAdapter {
 
 impl<C, F> Editor for SlintEditor<C, F>
 where
-    C: slint::ComponentHandle + 'static,
+    C: SlintComponent,
     F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
 {
     fn spawn(
@@ -67,17 +125,43 @@ where
         parent: ParentWindowHandle,
         context: Arc<dyn GuiContext>,
     ) -> Box<dyn std::any::Any + Send> {
-        // Ensure the Slint platform is set up
-        ensure_slint_platform();
+        // Ensure the Slint platform is set up. Without it no window can be opened, so an empty
+        // handle is returned instead and the error is recorded on the state.
+        self.slint_state.clear_last_error();
+        if let Err(error) = ensure_slint_platform() {
+            self.slint_state.report_error(error);
+            return Box::new(());
+        }
+        if let Some(assets) = &self.options.assets {
+            assets.register_fonts();
+        }
+
+        if self.slint_state.is_open() {
+            warn_log!("The editor was spawned again while still open, replacing the old window");
+        }
+        let spawn_token = self.spawned_windows.register();
 
         let (unscaled_width, unscaled_height) = self.slint_state.scaled_logical_size();
-        let scaling_factor = self.scaling_factor.load();
+        let (scaling_factor, scale_source) = match self.options.effective_scale_policy() {
+            ScalePolicy::Automatic => match self.scaling_factor.load() {
+                Some(factor) => (Some(factor), SlintScaleSource::Host),
+                None => (None, SlintScaleSource::System),
+            },
+            ScalePolicy::System => (None, SlintScaleSource::System),
+            ScalePolicy::Fixed(factor) => (Some(factor as f32), SlintScaleSource::Fixed),
+        };
+        // When baseview picks the system scale factor, it only reports it with the first `Resized`
+        // event. Looking it up here lets the first frame already be drawn at the right size.
+        let initial_scale_factor = scaling_factor.or_else(|| parent_scale_factor(parent));
 
         let gui_context = Arc::clone(&context);
         let slint_state = Arc::clone(&self.slint_state);
         let component_factory = Arc::clone(&self.component_factory);
         let on_param_values_changed = self.on_param_values_changed.clone();
         let emit_parameters_changed_event = Arc::clone(&self.emit_parameters_changed_event);
+        let options = self.options.clone();
+        let render_target = self.render_target.clone();
+        let handler_spawn_token = spawn_token.clone();
 
         // Create the mouse control that will be passed to the component factory
         let mouse_control = SlintMouseControl::new();
@@ -85,7 +169,7 @@ where
         let window = baseview::Window::open_parented(
             &ParentWindowHandleAdapter(parent),
             WindowOpenOptions {
-                title: String::from("Slint Plugin Window"),
+                title: self.options.window_title.clone(),
                 // Baseview should be doing the DPI scaling for us
                 size: Size::new(unscaled_width as f64, unscaled_height as f64),
                 // NOTE: For some reason passing 1.0 here causes the UI to be scaled on macOS but
@@ -101,16 +185,21 @@ where
                     slint_state,
                     component_factory,
                     mouse_control,
-                    scaling_factor.unwrap_or(1.0),
+                    initial_scale_factor,
+                    scale_source,
                     on_param_values_changed,
                     emit_parameters_changed_event,
+                    options,
+                    render_target,
+                    handler_spawn_token,
                 )
             },
         );
 
-        self.slint_state.open.store(true, Ordering::Release);
+        self.slint_state.set_open(true);
         Box::new(SlintEditorHandle {
             slint_state: self.slint_state.clone(),
+            spawn_token,
             window,
         })
     }
@@ -126,31 +215,40 @@ where
             return false;
         }
 
+        // The host's scale factor would be ignored anyway when the scale policy is set explicitly
+        if self.options.effective_scale_policy() != ScalePolicy::Automatic {
+            return false;
+        }
+
         self.scaling_factor.store(Some(factor));
         true
     }
 
     fn param_value_changed(&self, id: &str, _normalized_value: f32) {
         // Set the flag - the window handler will check this in on_frame and call the callback
-        nih_plug::debug::nih_log!("param_value_changed: {}", id);
+        trace_log!("param_value_changed: {}", id);
         self.emit_parameters_changed_event
             .store(true, Ordering::Relaxed);
+        self.slint_state.set_needs_redraw(true);
     }
 
     fn param_modulation_changed(&self, _id: &str, _modulation_offset: f32) {
         self.emit_parameters_changed_event
             .store(true, Ordering::Relaxed);
+        self.slint_state.set_needs_redraw(true);
     }
 
     fn param_values_changed(&self) {
         self.emit_parameters_changed_event
             .store(true, Ordering::Relaxed);
+        self.slint_state.set_needs_redraw(true);
     }
 }
 
 /// The window handle used for [`SlintEditor`].
 struct SlintEditorHandle {
     slint_state: Arc<SlintState>,
+    spawn_token: SpawnToken,
     window: WindowHandle,
 }
 
@@ -160,8 +258,45 @@ unsafe impl Send for SlintEditorHandle {}
 
 impl Drop for SlintEditorHandle {
     fn drop(&mut self) {
-        self.slint_state.open.store(false, Ordering::Release);
+        self.spawn_token.release(&self.slint_state);
         // XXX: This should automatically happen when the handle gets dropped, but apparently not
         self.window.close();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn double_spawn_replaces_the_previous_window() {
+        let slint_state = SlintState::from_size(100, 100);
+        let spawned_windows = Arc::new(SpawnedWindows::default());
+        let open_changes = Arc::new(AtomicUsize::new(0));
+        slint_state.set_on_open_changed({
+            let open_changes = Arc::clone(&open_changes);
+            move |_| {
+                open_changes.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        let first = spawned_windows.register();
+        slint_state.set_open(true);
+        assert!(first.is_current());
+
+        // The host spawns the editor again before dropping the first handle
+        let second = spawned_windows.register();
+        slint_state.set_open(true);
+        assert!(!first.is_current());
+        assert!(second.is_current());
+        assert_eq!(open_changes.load(Ordering::Relaxed), 1);
+
+        // Dropping the stale handle must not mark the replacement as closed
+        first.release(&slint_state);
+        assert!(slint_state.is_open());
+        second.release(&slint_state);
+        assert!(!slint_state.is_open());
+        assert_eq!(open_changes.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,61 @@
+//! Errors that can occur while opening or running a Slint editor.
+
+use std::fmt;
+
+/// An error that occurred while opening or running a Slint editor. None of these take down the
+/// host: the editor logs them, keeps running in a degraded state where possible, and records the
+/// latest one in [`SlintState::last_error()`][crate::SlintState::last_error()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlintEditorError {
+    /// Slint's platform could not be initialized. This usually means something else in the
+    /// process has already set a different Slint platform. No editor can be opened in this case.
+    PlatformAlreadySet(String),
+    /// Baseview reported a window handle type that can't be drawn to, for example from a
+    /// windowing system softbuffer doesn't support.
+    UnsupportedWindowHandle(String),
+    /// The softbuffer context used to draw to the window could not be created. The window stays
+    /// blank.
+    SoftbufferContext(String),
+    /// The softbuffer surface used to draw to the window could not be created. The window stays
+    /// blank.
+    SoftbufferSurface(String),
+    /// Presenting frames to the softbuffer surface kept failing even after the surface was
+    /// recreated several times. The window may stop updating.
+    SoftbufferPresent(String),
+    /// The component factory panicked or its component could not be shown. An error panel with
+    /// this message is shown in place of the component.
+    ComponentFactory(String),
+    /// One of the component's Slint timer or animation callbacks panicked. The panic is caught so
+    /// the rest of the frame still runs, and the editor keeps running.
+    TimerCallback(String),
+}
+
+impl fmt::Display for SlintEditorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlintEditorError::PlatformAlreadySet(message) => {
+                write!(f, "could not set the Slint platform: {message}")
+            }
+            SlintEditorError::UnsupportedWindowHandle(message) => {
+                write!(f, "unsupported window handle: {message}")
+            }
+            SlintEditorError::SoftbufferContext(message) => {
+                write!(f, "could not create the softbuffer context: {message}")
+            }
+            SlintEditorError::SoftbufferSurface(message) => {
+                write!(f, "could not create the softbuffer surface: {message}")
+            }
+            SlintEditorError::SoftbufferPresent(message) => {
+                write!(f, "could not present to the softbuffer surface: {message}")
+            }
+            SlintEditorError::ComponentFactory(message) => {
+                write!(f, "could not create the Slint component: {message}")
+            }
+            SlintEditorError::TimerCallback(message) => {
+                write!(f, "a Slint timer callback panicked: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SlintEditorError {}
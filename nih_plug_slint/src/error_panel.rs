@@ -0,0 +1,31 @@
+//! A minimal built-in panel that is shown in place of the plugin's Slint component when creating
+//! that component fails.
+
+slint::slint! {
+    export component ErrorPanel inherits Window {
+        in property <string> message;
+
+        background: #2b2020;
+
+        VerticalLayout {
+            alignment: center;
+            padding: 16px;
+            spacing: 8px;
+
+            Text {
+                text: "The editor failed to load";
+                color: #ffb4b4;
+                font-size: 16px;
+                font-weight: 700;
+                horizontal-alignment: center;
+            }
+
+            Text {
+                text: root.message;
+                color: #e0e0e0;
+                wrap: word-wrap;
+                horizontal-alignment: center;
+            }
+        }
+    }
+}
@@ -1,9 +1,31 @@
 //! Event translation from baseview events to Slint WindowEvents.
 
-use keyboard_types::{Key, KeyState, KeyboardEvent};
+use crate::{SlintKeyMapping, SlintScrollDelta};
+use keyboard_types::{Key, KeyState, KeyboardEvent, Location, Modifiers};
 use slint::platform::WindowEvent;
 use slint::{LogicalPosition, LogicalSize};
 
+/// The number of logical pixels a single line of line-based scrolling corresponds to.
+pub const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
+/// Interpret a baseview scroll delta both in pixels and in wheel steps. Line-based deltas from
+/// notched wheels are converted to pixels and pixel-based deltas from trackpads are converted to
+/// steps using [`PIXELS_PER_SCROLL_LINE`].
+pub fn scroll_delta(delta: &baseview::ScrollDelta) -> SlintScrollDelta {
+    match *delta {
+        baseview::ScrollDelta::Lines { x, y } => SlintScrollDelta {
+            pixels: (x * PIXELS_PER_SCROLL_LINE, y * PIXELS_PER_SCROLL_LINE),
+            steps: (x, y),
+            discrete: true,
+        },
+        baseview::ScrollDelta::Pixels { x, y } => SlintScrollDelta {
+            pixels: (x, y),
+            steps: (x / PIXELS_PER_SCROLL_LINE, y / PIXELS_PER_SCROLL_LINE),
+            discrete: false,
+        },
+    }
+}
+
 /// Translates a baseview event to a Slint WindowEvent.
 /// Returns `None` if the event doesn't have a corresponding Slint event.
 ///
@@ -13,7 +35,17 @@ use slint::{LogicalPosition, LogicalSize};
 pub fn translate_event(event: &baseview::Event, scale_factor: f32, is_button_pressed: bool) -> Option<WindowEvent> {
     match event {
         baseview::Event::Mouse(mouse_event) => translate_mouse_event(mouse_event, scale_factor, is_button_pressed),
-        // Don't translate keyboard events — let them pass through to the DAW host
+        // The context menu key opens the menu of the focused `ContextMenuArea`. Slint handles
+        // that itself once it receives the key.
+        baseview::Event::Keyboard(keyboard_event) if keyboard_event.key == Key::ContextMenu => {
+            translate_keyboard_event(keyboard_event)
+        }
+        // Modifier keys are sent with their location, so Slint can tell left and right Shift,
+        // Control, and Meta apart. The window handler still passes them on to the host as well.
+        baseview::Event::Keyboard(keyboard_event) if is_modifier_key(&keyboard_event.key) => {
+            translate_keyboard_event(keyboard_event)
+        }
+        // Don't translate other keyboard events — let them pass through to the DAW host
         baseview::Event::Keyboard(_) => None,
         baseview::Event::Window(window_event) => translate_window_event(window_event, scale_factor),
     }
@@ -48,13 +80,7 @@ fn translate_mouse_event(event: &baseview::MouseEvent, _scale_factor: f32, is_bu
             })
         }
         baseview::MouseEvent::WheelScrolled { delta, modifiers: _ } => {
-            let (delta_x, delta_y) = match delta {
-                baseview::ScrollDelta::Lines { x, y } => {
-                    // Convert lines to pixels (typical line height)
-                    (*x as f32 * 20.0, *y as f32 * 20.0)
-                }
-                baseview::ScrollDelta::Pixels { x, y } => (*x as f32, *y as f32),
-            };
+            let (delta_x, delta_y) = scroll_delta(delta).pixels;
             Some(WindowEvent::PointerScrolled {
                 position: LogicalPosition::default(),
                 delta_x,
@@ -80,6 +106,67 @@ fn translate_mouse_event(event: &baseview::MouseEvent, _scale_factor: f32, is_bu
     }
 }
 
+/// Replace the position of a pointer event with `position`. Baseview's button and scroll events
+/// don't carry a position, so they're translated with a placeholder that has to be filled in from
+/// the last known cursor position before they're dispatched. Other events are left untouched.
+pub fn set_pointer_position(event: &mut WindowEvent, position: LogicalPosition) {
+    match event {
+        WindowEvent::PointerMoved { position: p }
+        | WindowEvent::PointerPressed { position: p, .. }
+        | WindowEvent::PointerReleased { position: p, .. }
+        | WindowEvent::PointerScrolled { position: p, .. } => *p = position,
+        _ => {}
+    }
+}
+
+/// Whether `key` is one of the modifier keys [`translate_event()`] forwards to Slint.
+pub fn is_modifier_key(key: &Key) -> bool {
+    matches!(
+        key,
+        Key::Shift | Key::Control | Key::Alt | Key::AltGraph | Key::Meta | Key::Super
+    )
+}
+
+/// The key events that bring Slint's modifier state from `old` to `new`. Modifier key events are
+/// forwarded to Slint, but some platforms only report modifier changes through the modifiers on
+/// mouse events, and key releases are lost when they happen while another window has focus. This
+/// keeps the modifiers TouchAreas expose as `event.modifiers` in sync with the pointer events.
+///
+/// The modifiers on mouse events don't say which side's key is held, so presses are sent for the
+/// left hand side keys. Releases are sent for both sides, since either may have been pressed.
+///
+/// The events only ever carry Slint's own key codes for the modifier keys, never the text a key
+/// would produce. Slint uses these to update its modifier state, and text inputs ignore them, so
+/// pressing and releasing a modifier on its own doesn't insert anything into a focused text field.
+pub fn modifier_changes(old: Modifiers, new: Modifiers) -> Vec<WindowEvent> {
+    const MODIFIER_KEYS: [(Modifiers, Key); 4] = [
+        (Modifiers::SHIFT, Key::Shift),
+        (Modifiers::CONTROL, Key::Control),
+        (Modifiers::ALT, Key::Alt),
+        (Modifiers::META, Key::Meta),
+    ];
+
+    let mut events = Vec::new();
+    for (modifier, key) in MODIFIER_KEYS {
+        if old.contains(modifier) == new.contains(modifier) {
+            continue;
+        }
+
+        let left = key_to_text(&key, Location::Left);
+        if new.contains(modifier) {
+            events.push(WindowEvent::KeyPressed { text: left.into() });
+        } else {
+            let right = key_to_text(&key, Location::Right);
+            if right != left {
+                events.push(WindowEvent::KeyReleased { text: right.into() });
+            }
+            events.push(WindowEvent::KeyReleased { text: left.into() });
+        }
+    }
+
+    events
+}
+
 fn translate_mouse_button(button: baseview::MouseButton) -> Option<slint::platform::PointerEventButton> {
     match button {
         baseview::MouseButton::Left => Some(slint::platform::PointerEventButton::Left),
@@ -91,23 +178,49 @@ fn translate_mouse_button(button: baseview::MouseButton) -> Option<slint::platfo
     }
 }
 
+/// Translate a keyboard event using a plugin-provided [`SlintKeyMapping`]. Returns `None` if the
+/// mapping doesn't handle the key, in which case [`translate_event()`] applies.
+pub fn translate_mapped_key_event(
+    event: &KeyboardEvent,
+    key_mapping: &SlintKeyMapping,
+) -> Option<WindowEvent> {
+    let text = key_mapping.map(&event.key, event.modifiers)?.into();
+    match event.state {
+        KeyState::Down => Some(WindowEvent::KeyPressed { text }),
+        KeyState::Up => Some(WindowEvent::KeyReleased { text }),
+    }
+}
+
 fn translate_keyboard_event(event: &KeyboardEvent) -> Option<WindowEvent> {
-    let text = key_to_text(&event.key);
+    let text = key_to_text(&event.key, event.location);
     match event.state {
         KeyState::Down => Some(WindowEvent::KeyPressed { text: text.into() }),
         KeyState::Up => Some(WindowEvent::KeyReleased { text: text.into() }),
     }
 }
 
-fn key_to_text(key: &Key) -> String {
+fn key_to_text(key: &Key, location: Location) -> String {
+    use slint::platform::Key as SlintKey;
 
+    // Slint has separate key codes for the right hand side modifier keys
+    let is_right = location == Location::Right;
     match key {
+        Key::Shift if is_right => char::from(SlintKey::ShiftR).to_string(),
+        Key::Shift => char::from(SlintKey::Shift).to_string(),
+        Key::Control if is_right => char::from(SlintKey::ControlR).to_string(),
+        Key::Control => char::from(SlintKey::Control).to_string(),
+        // Slint doesn't have a separate right Alt key, and on many layouts right Alt is AltGr
+        Key::Alt => char::from(SlintKey::Alt).to_string(),
+        Key::AltGraph => char::from(SlintKey::AltGr).to_string(),
+        Key::Meta | Key::Super if is_right => char::from(SlintKey::MetaR).to_string(),
+        Key::Meta | Key::Super => char::from(SlintKey::Meta).to_string(),
         Key::Character(s) => s.clone(),
         Key::Enter => "\n".to_string(),
         Key::Tab => "\t".to_string(),
         Key::Backspace => "\u{0008}".to_string(), // Backspace character
         Key::Delete => "\u{007F}".to_string(),    // Delete character
         Key::Escape => "\u{001B}".to_string(),    // Escape character
+        Key::ContextMenu => char::from(SlintKey::Menu).to_string(),
         Key::ArrowUp => String::new(),            // Special keys don't produce text
         Key::ArrowDown => String::new(),
         Key::ArrowLeft => String::new(),
@@ -130,6 +243,282 @@ fn translate_window_event(event: &baseview::WindowEvent, _scale_factor: f32) ->
         }
         baseview::WindowEvent::Focused => Some(WindowEvent::WindowActiveChanged(true)),
         baseview::WindowEvent::Unfocused => Some(WindowEvent::WindowActiveChanged(false)),
-        baseview::WindowEvent::WillClose => Some(WindowEvent::CloseRequested),
+        // This is handled by the window handler since it requires tearing down the component
+        baseview::WindowEvent::WillClose => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_window::show_in_test_window;
+
+    slint::slint! {
+        export component HoverTest inherits Window {
+            width: 100px;
+            height: 100px;
+
+            out property <bool> hovered: area.has-hover;
+
+            area := TouchArea {}
+        }
+    }
+
+    #[test]
+    fn modifier_changes_press_and_release() {
+        let shift = char::from(slint::platform::Key::Shift).to_string();
+        let shift_r = char::from(slint::platform::Key::ShiftR).to_string();
+        let control = char::from(slint::platform::Key::Control).to_string();
+
+        assert!(modifier_changes(Modifiers::SHIFT, Modifiers::SHIFT).is_empty());
+        assert_eq!(
+            modifier_changes(Modifiers::SHIFT, Modifiers::CONTROL),
+            vec![
+                WindowEvent::KeyReleased {
+                    text: shift_r.into()
+                },
+                WindowEvent::KeyReleased { text: shift.into() },
+                WindowEvent::KeyPressed { text: control.into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn right_modifiers_keep_their_location() {
+        let key_event = |key, state| {
+            baseview::Event::Keyboard(KeyboardEvent {
+                key,
+                state,
+                location: Location::Right,
+                ..Default::default()
+            })
+        };
+        let shift_r = char::from(slint::platform::Key::ShiftR).to_string();
+        let control_r = char::from(slint::platform::Key::ControlR).to_string();
+
+        assert_eq!(
+            translate_event(&key_event(Key::Shift, KeyState::Down), 1.0, false),
+            Some(WindowEvent::KeyPressed {
+                text: shift_r.clone().into()
+            })
+        );
+        assert_eq!(
+            translate_event(&key_event(Key::Shift, KeyState::Up), 1.0, false),
+            Some(WindowEvent::KeyReleased {
+                text: shift_r.into()
+            })
+        );
+        assert_eq!(
+            translate_event(&key_event(Key::Control, KeyState::Down), 1.0, false),
+            Some(WindowEvent::KeyPressed {
+                text: control_r.into()
+            })
+        );
+    }
+
+    #[test]
+    fn only_context_menu_and_modifier_keys_are_translated() {
+        let key_event = |key| {
+            baseview::Event::Keyboard(KeyboardEvent {
+                key,
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            translate_event(&key_event(Key::ContextMenu), 1.0, false),
+            Some(WindowEvent::KeyPressed {
+                text: char::from(slint::platform::Key::Menu).to_string().into()
+            })
+        );
+        assert_eq!(
+            translate_event(&key_event(Key::Character("a".into())), 1.0, false),
+            None
+        );
+    }
+
+    #[test]
+    fn key_mappings_override_the_default_translation() {
+        let key_mapping = SlintKeyMapping::new(|key, modifiers| match key {
+            Key::Tab if !modifiers.contains(Modifiers::SHIFT) => Some(String::from("\t")),
+            _ => None,
+        });
+        let key_event = |key, modifiers| KeyboardEvent {
+            key,
+            modifiers,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            translate_mapped_key_event(&key_event(Key::Tab, Modifiers::empty()), &key_mapping),
+            Some(WindowEvent::KeyPressed { text: "\t".into() })
+        );
+        assert_eq!(
+            translate_mapped_key_event(&key_event(Key::Tab, Modifiers::SHIFT), &key_mapping),
+            None
+        );
+        assert_eq!(
+            translate_mapped_key_event(&key_event(Key::Enter, Modifiers::empty()), &key_mapping),
+            None
+        );
+    }
+
+    #[test]
+    fn pointer_positions_keep_subpixel_precision() {
+        let event = baseview::Event::Mouse(baseview::MouseEvent::CursorMoved {
+            position: baseview::Point::new(10.25, 20.75),
+            modifiers: Modifiers::empty(),
+        });
+
+        assert_eq!(
+            translate_event(&event, 2.0, false),
+            Some(WindowEvent::PointerMoved {
+                position: LogicalPosition::new(10.25, 20.75)
+            })
+        );
+    }
+
+    #[test]
+    fn scrolling_reports_pixels_and_steps() {
+        let mouse_control = crate::SlintMouseControl::new();
+
+        // A trackpad scrolls smoothly, so steps are accumulated
+        let trackpad = baseview::ScrollDelta::Pixels { x: 0.0, y: 30.0 };
+        mouse_control.add_scroll(scroll_delta(&trackpad));
+        assert_eq!(mouse_control.take_scroll_steps(), (0, 1));
+
+        let wheel = baseview::ScrollDelta::Lines { x: 0.0, y: -1.0 };
+        mouse_control.add_scroll(scroll_delta(&wheel));
+        assert_eq!(
+            mouse_control.last_scroll(),
+            Some(SlintScrollDelta {
+                pixels: (0.0, -PIXELS_PER_SCROLL_LINE),
+                steps: (0.0, -1.0),
+                discrete: true,
+            })
+        );
+        // Half a step was left over from the trackpad
+        assert_eq!(mouse_control.take_scroll_steps(), (0, 0));
+    }
+
+    slint::slint! {
+        export component DragTest inherits Window {
+            width: 100px;
+            height: 100px;
+
+            out property <bool> pressed: area.pressed;
+            out property <length> drag-x: area.mouse-x;
+            out property <int> moves;
+
+            area := TouchArea {
+                moved => { root.moves += 1; }
+            }
+        }
+    }
+
+    #[test]
+    fn press_move_release_drags() {
+        let (window, ui) = show_in_test_window(|| DragTest::new().unwrap());
+
+        // Button events are translated without a position, just like in the window handler
+        let dispatch = |event: baseview::MouseEvent, x: f32, is_button_pressed: bool| {
+            let mut event =
+                translate_event(&baseview::Event::Mouse(event), 1.0, is_button_pressed).unwrap();
+            set_pointer_position(&mut event, LogicalPosition::new(x, 50.0));
+            window.dispatch_event(event);
+        };
+        let moved = |x| baseview::MouseEvent::CursorMoved {
+            position: baseview::Point::new(x as f64, 50.0),
+            modifiers: Modifiers::empty(),
+        };
+        let left = baseview::MouseButton::Left;
+
+        dispatch(moved(10.0), 10.0, false);
+        dispatch(
+            baseview::MouseEvent::ButtonPressed {
+                button: left,
+                modifiers: Modifiers::empty(),
+            },
+            10.0,
+            true,
+        );
+        assert!(ui.get_pressed());
+
+        // The drag continues even outside of the window
+        for x in [30.0, 60.0, 150.0] {
+            dispatch(moved(x), x, true);
+            assert!(ui.get_pressed());
+            assert_eq!(ui.get_drag_x(), x);
+        }
+        assert_eq!(ui.get_moves(), 3);
+
+        dispatch(
+            baseview::MouseEvent::ButtonReleased {
+                button: left,
+                modifiers: Modifiers::empty(),
+            },
+            150.0,
+            false,
+        );
+        assert!(!ui.get_pressed());
+    }
+
+    #[test]
+    fn cursor_left_clears_hover() {
+        let (window, ui) = show_in_test_window(|| HoverTest::new().unwrap());
+
+        let moved = translate_event(
+            &baseview::Event::Mouse(baseview::MouseEvent::CursorMoved {
+                position: baseview::Point::new(50.0, 50.0),
+                modifiers: Modifiers::empty(),
+            }),
+            1.0,
+            false,
+        )
+        .unwrap();
+        window.dispatch_event(moved);
+        assert!(ui.get_hovered());
+
+        let left = translate_event(
+            &baseview::Event::Mouse(baseview::MouseEvent::CursorLeft),
+            1.0,
+            false,
+        )
+        .unwrap();
+        window.dispatch_event(left);
+        assert!(!ui.get_hovered());
+    }
+
+    slint::slint! {
+        export component TextTest inherits Window {
+            width: 100px;
+            height: 100px;
+
+            out property <string> text: input.text;
+
+            input := TextInput {}
+
+            init => { input.focus(); }
+        }
+    }
+
+    #[test]
+    fn modifier_only_presses_insert_no_text() {
+        let (window, ui) = show_in_test_window(|| TextTest::new().unwrap());
+
+        for all_modifiers in [Modifiers::SHIFT, Modifiers::all()] {
+            let events = modifier_changes(Modifiers::empty(), all_modifiers)
+                .into_iter()
+                .chain(modifier_changes(all_modifiers, Modifiers::empty()));
+            for event in events {
+                window.dispatch_event(event);
+            }
+        }
+        assert_eq!(ui.get_text(), "");
+
+        // The text input does have focus, so regular key presses do insert text
+        window.dispatch_event(WindowEvent::KeyPressed { text: "A".into() });
+        window.dispatch_event(WindowEvent::KeyReleased { text: "A".into() });
+        assert_eq!(ui.get_text(), "A");
     }
 }
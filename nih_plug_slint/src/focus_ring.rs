@@ -0,0 +1,456 @@
+//! Keyboard navigation between custom-drawn parameter controls.
+
+use keyboard_types::{Key, KeyState, KeyboardEvent, Modifiers};
+use nih_plug::prelude::{GuiContext, Param, ParamPtr, Params};
+use parking_lot::Mutex;
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::callback::ComponentCallback;
+use crate::SlintComponent;
+
+/// The parameter controls that can be focused with the keyboard, in the order Tab moves through
+/// them. Set it as [`SlintEditorOptions::focus_ring`][crate::SlintEditorOptions::focus_ring] to
+/// have Tab focus the next control and Shift+Tab the previous one, wrapping around at the ends.
+/// Escape removes the focus again. These keys are only taken from the host while the ring has
+/// controls registered, and Escape only while a control is focused.
+///
+/// Slint moves the focus between its own widgets, but custom-drawn knobs and sliders usually
+/// aren't focusable. The ring doesn't touch Slint's focus at all. Instead it tracks which control
+/// is focused by its index, and the component draws the focus indicator itself, typically from
+/// an `in property <int> focused-control` that's -1 when no control is focused. Set that property
+/// from [`set_on_focus_changed()`][Self::set_on_focus_changed()]. Controls that are clicked
+/// should usually take the focus as well, see [`focus_param()`][Self::focus_param()].
+///
+/// The ring is created for the plugin's [`Params`] object and keeps it alive. Controls are
+/// registered with [`register()`][Self::register()], which returns the control's index. Register
+/// them in the order they appear in the GUI, usually from left to right and top to bottom. Only
+/// parameters from the ring's `Params` object can be registered. Clones share the same controls
+/// and focus.
+///
+/// With [`set_arrow_key_nudging()`][Self::set_arrow_key_nudging()] enabled, the arrow keys also
+/// adjust the focused control's parameter, so all controls in the ring can be edited without a
+/// mouse.
+///
+/// # Example
+///
+/// ```ignore
+/// // In `Plugin::editor()`:
+/// let focus_ring = SlintFocusRing::new(self.params.clone());
+/// focus_ring.register(&self.params.cutoff); // Some(0)
+/// focus_ring.register(&self.params.resonance); // Some(1)
+/// focus_ring.set_on_focus_changed(|ui: &MyPluginUI, focused| {
+///     ui.set_focused_control(focused.map_or(-1, |index| index as i32));
+/// });
+///
+/// create_slint_editor_with_options(
+///     self.params.editor_state.clone(),
+///     {
+///         let (focus_ring, params) = (focus_ring.clone(), self.params.clone());
+///         move |gui_context, mouse_control| {
+///             let ui = MyPluginUI::new().unwrap();
+///             ui.on_cutoff_pressed({
+///                 let (focus_ring, params) = (focus_ring.clone(), params.clone());
+///                 move || {
+///                     focus_ring.focus_param(&params.cutoff);
+///                 }
+///             });
+///             ui
+///         }
+///     },
+///     None,
+///     SlintEditorOptions {
+///         focus_ring: Some(focus_ring),
+///         ..Default::default()
+///     },
+/// )
+/// ```
+#[derive(Clone)]
+pub struct SlintFocusRing(Arc<FocusRingInner>);
+
+struct FocusRingInner {
+    /// The plugin's parameters. The registered parameters are looked up in here, which also keeps
+    /// them alive for as long as the ring exists.
+    params: Arc<dyn Params>,
+    state: Mutex<FocusRingState>,
+    /// Set when the focus changed and the focus changed callback hasn't been called for it yet.
+    changed: AtomicBool,
+    /// Whether the arrow keys adjust the focused parameter. See
+    /// [`SlintFocusRing::set_arrow_key_nudging()`].
+    arrow_key_nudging: AtomicBool,
+    /// Receives the component as a `&dyn Any` along with the focused control's index.
+    on_focus_changed: ComponentCallback<Option<usize>>,
+}
+
+#[derive(Default)]
+struct FocusRingState {
+    /// The IDs of the registered parameters, in focus order.
+    param_ids: Vec<String>,
+    focused: Option<usize>,
+}
+
+impl std::fmt::Debug for SlintFocusRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.0.state.lock();
+        f.debug_struct("SlintFocusRing")
+            .field("controls", &state.param_ids)
+            .field("focused", &state.focused)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two focus rings are only equal if they are clones of each other.
+impl PartialEq for SlintFocusRing {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl SlintFocusRing {
+    /// Create an empty focus ring for the parameters in `params`.
+    pub fn new<P: Params>(params: Arc<P>) -> Self {
+        Self(Arc::new(FocusRingInner {
+            params,
+            state: Mutex::default(),
+            changed: AtomicBool::new(false),
+            arrow_key_nudging: AtomicBool::new(false),
+            on_focus_changed: ComponentCallback::default(),
+        }))
+    }
+
+    /// Add the control for `param` to the end of the ring and return its index. Registering the
+    /// same parameter again returns its existing index. Returns `None` if `param` is not part of
+    /// the ring's [`Params`] object.
+    pub fn register<P: Param>(&self, param: &P) -> Option<usize> {
+        let id = self.param_id(param.as_ptr())?;
+        let mut state = self.0.state.lock();
+        match state.param_ids.iter().position(|existing| *existing == id) {
+            Some(index) => Some(index),
+            None => {
+                state.param_ids.push(id);
+                Some(state.param_ids.len() - 1)
+            }
+        }
+    }
+
+    /// Remove all controls and the focus, for instance before registering the controls of a
+    /// different layout.
+    pub fn clear(&self) {
+        let mut state = self.0.state.lock();
+        state.param_ids.clear();
+        self.set_focused(&mut state, None);
+    }
+
+    /// The number of registered controls.
+    pub fn len(&self) -> usize {
+        self.0.state.lock().param_ids.len()
+    }
+
+    /// Whether no controls are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The index of the focused control, if any.
+    pub fn focused(&self) -> Option<usize> {
+        self.0.state.lock().focused
+    }
+
+    /// Focus the control at `index`, or remove the focus with `None`. Indices past the end of the
+    /// ring remove the focus as well.
+    pub fn focus(&self, index: Option<usize>) {
+        let mut state = self.0.state.lock();
+        let index = index.filter(|index| *index < state.param_ids.len());
+        self.set_focused(&mut state, index);
+    }
+
+    /// Focus the control for `param`. Returns `false` and leaves the focus unchanged if `param`
+    /// isn't registered.
+    pub fn focus_param<P: Param>(&self, param: &P) -> bool {
+        let Some(id) = self.param_id(param.as_ptr()) else {
+            return false;
+        };
+        let mut state = self.0.state.lock();
+        match state.param_ids.iter().position(|existing| *existing == id) {
+            Some(index) => {
+                self.set_focused(&mut state, Some(index));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Focus the next control, or the first one if none is focused.
+    pub fn focus_next(&self) {
+        let mut state = self.0.state.lock();
+        let len = state.param_ids.len();
+        if len > 0 {
+            let index = state.focused.map_or(0, |index| (index + 1) % len);
+            self.set_focused(&mut state, Some(index));
+        }
+    }
+
+    /// Focus the previous control, or the last one if none is focused.
+    pub fn focus_previous(&self) {
+        let mut state = self.0.state.lock();
+        let len = state.param_ids.len();
+        if len > 0 {
+            let index = state
+                .focused
+                .map_or(len - 1, |index| (index + len - 1) % len);
+            self.set_focused(&mut state, Some(index));
+        }
+    }
+
+    /// Set a callback that's invoked with the index of the focused control whenever the focus
+    /// changes, and with the current focus whenever the component is created. Use it to move the
+    /// component's focus indicator. The callback runs on the GUI thread at the start of the next
+    /// frame, so changing the focus from another thread is fine. Like the
+    /// [`SlintState` component callbacks][crate::SlintState#component-callbacks], `C` must be the
+    /// component factory's component type.
+    pub fn set_on_focus_changed<C, F>(&self, callback: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, Option<usize>) + Send + Sync + 'static,
+    {
+        self.0.on_focus_changed.set_for(callback);
+    }
+
+    /// Let the arrow keys adjust the focused control's parameter. Up and Right increase the value,
+    /// and Down and Left decrease it, each key press or key repeat being its own gesture.
+    /// Continuous parameters move by half a percent of their range, or by two percent while Shift
+    /// is held. Stepped parameters like [`IntParam`][nih_plug::prelude::IntParam] and
+    /// [`EnumParam`][nih_plug::prelude::EnumParam] always move by a single step. While a control
+    /// is focused, the arrow keys are no longer passed on to the host. Disabled by default.
+    pub fn set_arrow_key_nudging(&self, enabled: bool) {
+        self.0.arrow_key_nudging.store(enabled, Ordering::Relaxed);
+    }
+
+    /// The parameter of the focused control, if any. The pointer stays valid for as long as this
+    /// ring exists.
+    pub(crate) fn focused_param(&self) -> Option<ParamPtr> {
+        let id = {
+            let state = self.0.state.lock();
+            state.param_ids.get(state.focused?)?.clone()
+        };
+
+        self.0
+            .params
+            .param_map()
+            .into_iter()
+            .find(|(param_id, _, _)| *param_id == id)
+            .map(|(_, param_ptr, _)| param_ptr)
+    }
+
+    /// The ID of `param` in the ring's [`Params`] object, if it's part of it.
+    fn param_id(&self, param: ParamPtr) -> Option<String> {
+        self.0
+            .params
+            .param_map()
+            .into_iter()
+            .find(|(_, param_ptr, _)| *param_ptr == param)
+            .map(|(id, _, _)| id)
+    }
+
+    /// Move the focus in response to a key event. Returns whether the event was used and should
+    /// not be passed on to the host.
+    pub(crate) fn handle_key(&self, event: &KeyboardEvent) -> bool {
+        let relevant = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::META;
+        let modifiers = event.modifiers & relevant;
+        let pressed = event.state == KeyState::Down;
+
+        match event.key {
+            Key::Tab if (modifiers & !Modifiers::SHIFT).is_empty() && !self.is_empty() => {
+                if pressed && modifiers.contains(Modifiers::SHIFT) {
+                    self.focus_previous();
+                } else if pressed {
+                    self.focus_next();
+                }
+
+                true
+            }
+            Key::Escape if modifiers.is_empty() && pressed && self.focused().is_some() => {
+                self.focus(None);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Adjust the focused parameter in response to an arrow key event if
+    /// [`set_arrow_key_nudging()`][Self::set_arrow_key_nudging()] is enabled. Returns whether the
+    /// event was used and should not be passed on to the host.
+    pub(crate) fn nudge(&self, event: &KeyboardEvent, gui_context: &dyn GuiContext) -> bool {
+        if !self.0.arrow_key_nudging.load(Ordering::Relaxed) {
+            return false;
+        }
+        let (Some((increase, coarse)), Some(param)) =
+            (nudge_direction(event), self.focused_param())
+        else {
+            return false;
+        };
+
+        if event.state == KeyState::Down {
+            // SAFETY: The parameter belongs to the ring's `Params` object, which this keeps alive
+            unsafe {
+                let start = param.unmodulated_normalized_value();
+                let normalized = nudged_value(param, start, increase, coarse);
+                if normalized != start {
+                    gui_context.raw_begin_set_parameter(param);
+                    gui_context.raw_set_parameter_normalized(param, normalized);
+                    gui_context.raw_end_set_parameter(param);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Invoke the focus changed callback with `component` if the focus changed since the last
+    /// call, or regardless if `force` is set.
+    pub(crate) fn notify_focus_changed(&self, component: &dyn Any, force: bool) {
+        if !self.0.changed.swap(false, Ordering::AcqRel) && !force {
+            return;
+        }
+
+        self.0.on_focus_changed.call(component, self.focused());
+    }
+
+    fn set_focused(&self, state: &mut FocusRingState, focused: Option<usize>) {
+        if state.focused != focused {
+            state.focused = focused;
+            self.0.changed.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Whether an arrow key event increases or decreases the value, and whether Shift asks for a
+/// larger step. Returns `None` for other keys and for arrow keys with other modifiers.
+fn nudge_direction(event: &KeyboardEvent) -> Option<(bool, bool)> {
+    let relevant = Modifiers::CONTROL | Modifiers::ALT | Modifiers::META;
+    if event.modifiers.intersects(relevant) {
+        return None;
+    }
+
+    let increase = match event.key {
+        Key::ArrowUp | Key::ArrowRight => true,
+        Key::ArrowDown | Key::ArrowLeft => false,
+        _ => return None,
+    };
+
+    Some((increase, event.modifiers.contains(Modifiers::SHIFT)))
+}
+
+/// The normalized value one nudge moves `param` to from `from`. Stepped parameters ignore
+/// `coarse` and always move by a single step.
+///
+/// # Safety
+///
+/// `param` must point to a live parameter.
+unsafe fn nudged_value(param: ParamPtr, from: f32, increase: bool, coarse: bool) -> f32 {
+    if increase {
+        param.next_normalized_step(from, !coarse)
+    } else {
+        param.previous_normalized_step(from, !coarse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nih_plug::prelude::{BoolParam, FloatParam, FloatRange, IntParam, IntRange};
+
+    #[derive(Params)]
+    struct FilterParams {
+        #[id = "cutoff"]
+        cutoff: FloatParam,
+        #[id = "resonance"]
+        resonance: FloatParam,
+    }
+
+    #[test]
+    fn tab_cycles_through_the_controls() {
+        let params = Arc::new(FilterParams {
+            cutoff: FloatParam::new("Cutoff", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 }),
+            resonance: FloatParam::new("Resonance", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 }),
+        });
+        let (cutoff, resonance) = (&params.cutoff, &params.resonance);
+        let bypass = BoolParam::new("Bypass", false);
+        let key_event = |key, modifiers| KeyboardEvent {
+            key,
+            modifiers,
+            ..Default::default()
+        };
+        let tab = key_event(Key::Tab, Modifiers::empty());
+        let shift_tab = key_event(Key::Tab, Modifiers::SHIFT);
+
+        let focus_ring = SlintFocusRing::new(params.clone());
+        // Nothing is taken from the host while the ring is empty
+        assert!(!focus_ring.handle_key(&tab));
+
+        assert_eq!(focus_ring.register(cutoff), Some(0));
+        assert_eq!(focus_ring.register(resonance), Some(1));
+        assert_eq!(focus_ring.register(cutoff), Some(0));
+        // Parameters from elsewhere can't be registered
+        assert_eq!(focus_ring.register(&bypass), None);
+
+        assert!(focus_ring.handle_key(&shift_tab));
+        assert_eq!(focus_ring.focused(), Some(1));
+        assert!(focus_ring.handle_key(&tab));
+        assert_eq!(focus_ring.focused(), Some(0));
+        assert!(!focus_ring.handle_key(&key_event(Key::Tab, Modifiers::CONTROL)));
+        assert_eq!(focus_ring.focused(), Some(0));
+
+        assert!(!focus_ring.focus_param(&bypass));
+        assert!(focus_ring.focus_param(resonance));
+        assert_eq!(focus_ring.focused_param(), Some(resonance.as_ptr()));
+
+        assert!(focus_ring.handle_key(&key_event(Key::Escape, Modifiers::empty())));
+        assert_eq!(focus_ring.focused(), None);
+        assert!(!focus_ring.handle_key(&key_event(Key::Escape, Modifiers::empty())));
+    }
+
+    #[test]
+    fn arrow_keys_nudge_by_the_parameter_step() {
+        let gain = FloatParam::new("Gain", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 });
+        let voices = IntParam::new("Voices", 4, IntRange::Linear { min: 1, max: 9 });
+        let key_event = |key, modifiers| KeyboardEvent {
+            key,
+            modifiers,
+            ..Default::default()
+        };
+        let nudge = |param: ParamPtr, key, modifiers| {
+            let (increase, coarse) = nudge_direction(&key_event(key, modifiers)).unwrap();
+            // SAFETY: The parameters live until the end of the test
+            unsafe {
+                nudged_value(
+                    param,
+                    param.unmodulated_normalized_value(),
+                    increase,
+                    coarse,
+                )
+            }
+        };
+
+        assert!((nudge(gain.as_ptr(), Key::ArrowUp, Modifiers::empty()) - 0.505).abs() < 1e-5);
+        assert!((nudge(gain.as_ptr(), Key::ArrowLeft, Modifiers::SHIFT) - 0.48).abs() < 1e-5);
+        assert_eq!(
+            nudge(voices.as_ptr(), Key::ArrowRight, Modifiers::empty()),
+            0.5
+        );
+        assert_eq!(
+            nudge(voices.as_ptr(), Key::ArrowDown, Modifiers::SHIFT),
+            0.25
+        );
+
+        assert_eq!(
+            nudge_direction(&key_event(Key::ArrowUp, Modifiers::CONTROL)),
+            None
+        );
+        assert_eq!(
+            nudge_direction(&key_event(Key::Enter, Modifiers::empty())),
+            None
+        );
+    }
+}
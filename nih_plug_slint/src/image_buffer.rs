@@ -0,0 +1,147 @@
+//! Dynamically generated images, like spectrograms and waterfalls.
+
+use slint::{Image, Rgba8Pixel, SharedPixelBuffer};
+
+/// A fixed-size image that's regenerated from raw pixel data, for scopes, spectrograms, and
+/// other visualizations drawn in Rust and shown through a Slint `Image` element.
+///
+/// A `slint::Image` shares its pixels with the [`SharedPixelBuffer`] it was created from, so
+/// writing to a buffer that's still being displayed would force a copy. This keeps two buffers
+/// and alternates between them: every update writes to the buffer that's not currently shown
+/// and [`image()`][Self::image()] returns an image for the freshly written one. Once that image
+/// replaces the old one in the component's property, the old buffer is free to be written to
+/// again, so updating the image every frame does not allocate.
+///
+/// # Example
+///
+/// ```ignore
+/// // In the .slint file:
+/// // in property <image> spectrogram;
+/// // Image { source: root.spectrogram; image-rendering: pixelated; }
+///
+/// // In the component factory:
+/// let mut spectrogram = SlintImageBuffer::new(512, 256);
+/// let ui_ref = SlintComponentRef::new(&ui);
+/// timer.start(slint::TimerMode::Repeated, Duration::from_millis(16), move || {
+///     analyzer.draw_waterfall(&mut waterfall_data);
+///     if spectrogram.update_grayscale(&waterfall_data) {
+///         ui_ref.with_component(|ui| ui.set_spectrogram(spectrogram.image()));
+///     }
+/// });
+/// ```
+pub struct SlintImageBuffer {
+    buffers: [SharedPixelBuffer<Rgba8Pixel>; 2],
+    /// The index of the buffer that was written to last.
+    front: usize,
+}
+
+impl SlintImageBuffer {
+    /// Create a `width * height` image. All pixels start out transparent black.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            buffers: [
+                SharedPixelBuffer::new(width, height),
+                SharedPixelBuffer::new(width, height),
+            ],
+            front: 0,
+        }
+    }
+
+    /// The image's width in pixels.
+    pub fn width(&self) -> u32 {
+        self.buffers[self.front].width()
+    }
+
+    /// The image's height in pixels.
+    pub fn height(&self) -> u32 {
+        self.buffers[self.front].height()
+    }
+
+    /// The number of pixels in the image.
+    fn len(&self) -> usize {
+        self.width() as usize * self.height() as usize
+    }
+
+    /// Replace the image's contents with tightly packed RGBA data, with four bytes per pixel and
+    /// rows stored from top to bottom. Returns `false` and leaves the image untouched if `data`
+    /// doesn't contain exactly `width * height * 4` bytes.
+    pub fn update_rgba(&mut self, data: &[u8]) -> bool {
+        if data.len() != self.len() * 4 {
+            return false;
+        }
+
+        self.update_with(|pixels| {
+            for (pixel, rgba) in pixels.iter_mut().zip(data.chunks_exact(4)) {
+                *pixel = Rgba8Pixel::new(rgba[0], rgba[1], rgba[2], rgba[3]);
+            }
+        });
+
+        true
+    }
+
+    /// Replace the image's contents with opaque grayscale data, with one byte per pixel and rows
+    /// stored from top to bottom. Returns `false` and leaves the image untouched if `data` doesn't
+    /// contain exactly `width * height` bytes.
+    pub fn update_grayscale(&mut self, data: &[u8]) -> bool {
+        if data.len() != self.len() {
+            return false;
+        }
+
+        self.update_with(|pixels| {
+            for (pixel, &value) in pixels.iter_mut().zip(data) {
+                *pixel = Rgba8Pixel::new(value, value, value, 255);
+            }
+        });
+
+        true
+    }
+
+    /// Write the image's pixels directly. `f` receives all `width * height` pixels, with rows
+    /// stored from top to bottom. The pixels contain the image from two updates ago, so `f` should
+    /// overwrite every pixel.
+    pub fn update_with(&mut self, f: impl FnOnce(&mut [Rgba8Pixel])) {
+        self.front = 1 - self.front;
+        f(self.buffers[self.front].make_mut_slice());
+    }
+
+    /// Get a `slint::Image` showing the latest update. This is cheap since the image shares the
+    /// buffer's pixels. Assign it to the component's image property after every update to show
+    /// the new contents.
+    pub fn image(&self) -> Image {
+        Image::from_rgba8(self.buffers[self.front].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_mismatch_is_rejected() {
+        let mut buffer = SlintImageBuffer::new(4, 2);
+        assert!(!buffer.update_grayscale(&[0; 7]));
+        assert!(!buffer.update_rgba(&[0; 8]));
+        assert!(buffer.update_grayscale(&[128; 8]));
+        assert!(buffer.update_rgba(&[64; 32]));
+    }
+
+    #[test]
+    fn updates_reuse_buffers() {
+        let mut buffer = SlintImageBuffer::new(16, 16);
+        let allocations = [
+            buffer.buffers[0].as_bytes().as_ptr(),
+            buffer.buffers[1].as_bytes().as_ptr(),
+        ];
+
+        let mut image = buffer.image();
+        for value in 0..10 {
+            assert!(buffer.update_grayscale(&[value; 256]));
+            // The image is replaced like it would be in the component's property
+            let _ = std::mem::replace(&mut image, buffer.image());
+        }
+
+        assert_eq!(image.to_rgba8().unwrap().as_slice()[0], Rgba8Pixel::new(9, 9, 9, 255));
+        assert_eq!(buffer.buffers[0].as_bytes().as_ptr(), allocations[0]);
+        assert_eq!(buffer.buffers[1].as_bytes().as_ptr(), allocations[1]);
+    }
+}
@@ -0,0 +1,92 @@
+//! Loading large images without delaying the editor's window.
+
+use slint::{ComponentHandle, Image, Rgba8Pixel, SharedPixelBuffer};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::logging::{debug_log, warn_log};
+use crate::SlintState;
+
+/// Load the image at `path` on a background thread and pass it to `set_image` once it has been
+/// decoded. Decoding a large background image can take long enough to noticeably delay opening
+/// the editor, so this lets the component factory return right away. Until the image is ready,
+/// the property keeps whatever it was set to, usually a small placeholder image or a solid color
+/// from the `.slint` file.
+///
+/// `set_image` runs on the GUI thread at the start of the next frame after decoding finished,
+/// through the same queue as [`SlintState::update_component()`]. It's only called if `component`
+/// still exists at that point, so results for a component that was dropped because the editor
+/// was closed or rebuilt in the meantime are discarded. If the image can't be loaded, a warning
+/// is logged and `set_image` is never called, so the placeholder stays.
+///
+/// The image is decoded to RGBA pixels, so SVG images are rasterized at their natural size.
+/// Load SVGs that should stay sharp at any size with [`Image::load_from_path()`] instead, which
+/// is cheap for them anyway. Which other formats can be decoded depends on the image features
+/// enabled for Slint.
+///
+/// # Example
+///
+/// ```ignore
+/// // In the component factory:
+/// let ui = MyPluginUI::new().unwrap();
+/// nih_plug_slint::load_image_in_background(
+///     &editor_state,
+///     &ui,
+///     assets.path("background.png"),
+///     |ui: &MyPluginUI, image| ui.set_background(image),
+/// );
+/// ui
+/// ```
+pub fn load_image_in_background<C, F>(
+    slint_state: &Arc<SlintState>,
+    component: &C,
+    path: impl Into<PathBuf>,
+    set_image: F,
+) where
+    C: ComponentHandle + 'static,
+    F: FnOnce(&C, Image) + Send + 'static,
+{
+    let slint_state = Arc::clone(slint_state);
+    let component = component.as_weak();
+    let path = path.into();
+
+    let result = std::thread::Builder::new()
+        .name(String::from("nih_plug_slint image loader"))
+        .spawn(move || {
+            let Some(pixels) = decode_image(&path) else {
+                return;
+            };
+
+            debug_log!("Loaded the image '{}' in the background", path.display());
+            slint_state.queue_component_update(Box::new(move |_| {
+                // This is `None` if the component is gone or if the editor was reopened on
+                // another thread
+                if let Some(component) = component.upgrade() {
+                    set_image(&component, Image::from_rgba8(pixels));
+                }
+            }));
+        });
+    if let Err(err) = result {
+        warn_log!("Could not start the image loading thread: {}", err);
+    }
+}
+
+/// Decode the image at `path` to RGBA pixels, which unlike [`Image`] can be sent to another
+/// thread. Returns `None` and logs a warning if that fails.
+fn decode_image(path: &Path) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+    let pixels = Image::load_from_path(path)
+        .map_err(|err| format!("{err:?}"))
+        .and_then(|image| {
+            image
+                .to_rgba8()
+                .ok_or_else(|| String::from("the pixels are not accessible"))
+        });
+
+    match pixels {
+        Ok(pixels) => Some(pixels),
+        Err(err) => {
+            warn_log!("Could not load the image '{}': {}", path.display(), err);
+            None
+        }
+    }
+}
@@ -10,23 +10,121 @@
 //! conflict. Slint uses `yeslogic-fontconfig-sys` while iced uses `servo-fontconfig-sys`,
 //! and Cargo only allows one crate to link a given native library. If you need both GUI
 //! frameworks, you'll need to use separate workspaces.
+//!
+//! # Popups
+//!
+//! Slint `PopupWindow`s and `ComboBox` dropdowns are rendered inside of the editor window and get
+//! clipped to its bounds. See [`popup_position()`] for a helper to keep custom popups visible.
+//!
+//! # Window stacking
+//!
+//! The editor is always opened as a child window embedded in a window provided by the host or by
+//! the standalone wrapper, so its stacking order is decided by that parent window. Baseview does
+//! not offer a way to make windows always-on-top, so this crate can't request that either. On
+//! hosts with floating plugin windows, use the host's own "keep on top" setting instead.
+//!
+//! # Touch input
+//!
+//! Only a single pointer is supported. Baseview does not report touch events on any platform, so
+//! touch screens only reach the editor through the operating system's mouse emulation, which
+//! turns the first finger into mouse movement and clicks and drops any additional fingers. Slint's
+//! public platform API also has no way to dispatch more than one pointer. Multi-touch, like
+//! adjusting two knobs at once, can be added once both support it. Until then, controls meant
+//! for touch screens should work with single-finger drags and taps.
+//!
+//! # Clipboard
+//!
+//! Slint's text fields use the clipboard on their own. Outside of them, text can be copied to the
+//! clipboard with [`SlintParamContext::copy_params_to_clipboard()`], and whether the clipboard
+//! holds text can be checked through [`SlintState::clipboard_has_text()`]. Both work on macOS and
+//! Windows. On Linux the clipboard can't be accessed at all, so copying returns `false` and the
+//! clipboard always appears empty there. Copy buttons should offer the text in another way then,
+//! and paste buttons should not depend on it.
 
 #![allow(clippy::type_complexity)]
 
+use crate::callback::{Callback, ComponentCallback};
+use crate::logging::{error_log, warn_log};
+use crate::param_text::{format_param_values, parse_param_values};
 use crossbeam::atomic::AtomicCell;
 use nih_plug::params::persist::PersistentField;
-use nih_plug::prelude::{Editor, GuiContext, ParamSetter};
+use nih_plug::prelude::{Editor, GuiContext, Param, ParamFlags, ParamPtr, ParamSetter, Params};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, Ordering};
+use slint::{Rgb8Pixel, SharedPixelBuffer};
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+mod accelerators;
+mod accessibility;
+mod assets;
+mod bindings;
+mod callback;
+mod clipboard;
+mod component;
+mod component_ref;
+mod control_mapping;
+mod diagnostics;
 mod editor;
+mod error;
+mod error_panel;
 mod event_translation;
+mod focus_ring;
+mod image_buffer;
+mod image_loader;
+mod logging;
+mod options;
+mod overlay;
+mod param_text;
+mod parent_scale;
+mod pixel_buffer;
 mod platform;
+mod popup;
+mod property_cache;
+mod render_target;
+mod smoothing;
+mod splash;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(test)]
+mod test_window;
+mod thread_check;
+mod ui_state;
+mod window_access;
 mod window_handler;
+mod window_opacity;
+mod xy_pad;
 
+pub use accelerators::SlintShortcut;
+pub use accessibility::{announce, prefers_reduced_motion, set_reduced_motion};
+pub use assets::SlintAssetDirectory;
+pub use bindings::{is_syncing_from_host, sync_from_host};
+pub use component::SlintComponent;
+pub use component_ref::SlintComponentRef;
+pub use control_mapping::{normalized_from_position, ControlAxis};
+pub use diagnostics::{SlintDiagnostics, SlintRenderMode};
 pub use editor::ParamChangedCallback;
+pub use error::SlintEditorError;
+pub use focus_ring::SlintFocusRing;
+pub use image_buffer::SlintImageBuffer;
+pub use image_loader::load_image_in_background;
+pub use keyboard_types::{Key, Modifiers};
+pub use logging::{log_level, set_log_level, LogLevel};
+pub use options::{
+    ScalePolicy, SlintEditorOptions, SlintKeyMapping, UnboundedMovement, UnboundedRegion,
+    DEFAULT_DOUBLE_CLICK_INTERVAL,
+};
+pub use overlay::SlintOverlayFrame;
+pub use popup::popup_position;
+pub use property_cache::SlintPropertyCache;
+pub use render_target::SlintRenderTarget;
 pub use slint;
+pub use smoothing::SlintSmoothedValue;
+pub use splash::SlintSplash;
+pub use ui_state::SlintUiState;
+pub use window_access::SlintWindowAccess;
+pub use xy_pad::SlintXyPad;
 
 /// Control for unbounded mouse movement during drag operations.
 ///
@@ -51,11 +149,49 @@ pub use slint;
 ///     let mc = mouse_control.clone();
 ///     move || mc.disable_unbounded_movement()
 /// });
+///
+/// // Scale the drag delta down while Alt (or Cmd on macOS) is held
+/// ui.on_knob_dragged({
+///     let mc = mouse_control.clone();
+///     move |delta| {
+///         let delta = if mc.is_fine_adjustment() { delta * 0.1 } else { delta };
+///         // ...
+///     }
+/// });
 /// ```
+///
+/// # Pinch-to-zoom
+///
+/// Baseview does not report trackpad pinch or magnify gestures, and Slint has no pinch event, so
+/// there is no dedicated zoom callback. Windows precision touchpads and most Linux touchpad
+/// drivers report pinches as scroll events with Control held down, so zooming can be implemented
+/// by checking [`modifiers()`][Self::modifiers()] for [`Modifiers::CONTROL`] in a `TouchArea`'s
+/// `scroll-event` handler. The scroll event's position can be used as the zoom's focal point. On
+/// macOS pinch gestures are currently not reported at all.
+///
+/// # Scrolling
+///
+/// Slint's `scroll-event` reports movement in pixels, which suits smoothly scrolling areas like
+/// a zoomable waveform. Stepped controls like enum selectors want one step per wheel click
+/// instead. Both can live in the same editor: smooth areas use the event's `delta-x` and
+/// `delta-y` as usual, and stepped controls call [`take_scroll_steps()`][Self::take_scroll_steps()]
+/// from their handler. Controls that need to tell a notched wheel from a trackpad, for instance
+/// to only step on wheel clicks, can look at [`last_scroll()`][Self::last_scroll()], which
+/// describes the scroll event that's currently being handled in both units.
 #[derive(Clone)]
 pub struct SlintMouseControl {
     /// Request state: Option<(enable, restore_position)>
     request: Arc<AtomicCell<Option<(bool, bool)>>>,
+    /// The modifier keys held down during the last mouse or keyboard event.
+    modifiers: Arc<AtomicCell<Modifiers>>,
+    /// Scroll wheel movement in lines that hasn't been consumed yet, as `(x, y)`.
+    scroll_lines: Arc<AtomicCell<(f32, f32)>>,
+    /// The most recent scroll event. See [`last_scroll()`][Self::last_scroll()].
+    last_scroll: Arc<AtomicCell<Option<SlintScrollDelta>>>,
+    /// The last known pointer position in logical pixels, as `(x, y)`.
+    position: Arc<AtomicCell<(f32, f32)>>,
+    /// The window's scale factor, used to convert [`position`][Self::position] to device pixels.
+    scale_factor: Arc<AtomicCell<f32>>,
 }
 
 impl SlintMouseControl {
@@ -63,6 +199,11 @@ impl SlintMouseControl {
     pub(crate) fn new() -> Self {
         Self {
             request: Arc::new(AtomicCell::new(None)),
+            modifiers: Arc::new(AtomicCell::new(Modifiers::empty())),
+            scroll_lines: Arc::new(AtomicCell::new((0.0, 0.0))),
+            last_scroll: Arc::new(AtomicCell::new(None)),
+            position: Arc::new(AtomicCell::new((0.0, 0.0))),
+            scale_factor: Arc::new(AtomicCell::new(1.0)),
         }
     }
 
@@ -77,6 +218,9 @@ impl SlintMouseControl {
     ///
     /// * `restore_position` - If true, the cursor returns to its original position
     ///   when disabled. If false, the cursor stays where it ended up (accumulated position).
+    ///
+    /// The reported positions are not limited by the screens. On multi-monitor setups, use
+    /// [`SlintEditorOptions::unbounded_region`] to confine them to an area around the editor.
     pub fn enable_unbounded_movement(&self, restore_position: bool) {
         self.request.store(Some((true, restore_position)));
     }
@@ -94,12 +238,137 @@ impl SlintMouseControl {
         matches!(self.request.load(), Some((true, _)))
     }
 
+    /// The modifier keys that were held down during the last mouse or keyboard event. Slint's own
+    /// pointer events don't carry modifiers from the host, so use this to implement gestures like
+    /// Alt-drag for fine adjustment.
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers.load()
+    }
+
+    /// Whether the user is currently holding down the modifier for fine parameter adjustment.
+    /// This is Alt on all platforms, and additionally Cmd on macOS.
+    pub fn is_fine_adjustment(&self) -> bool {
+        let modifiers = self.modifiers();
+        if cfg!(target_os = "macos") {
+            modifiers.intersects(Modifiers::ALT | Modifiers::META)
+        } else {
+            modifiers.contains(Modifiers::ALT)
+        }
+    }
+
+    /// Update the currently held modifiers. Called by the window handler for every mouse and
+    /// keyboard event.
+    pub(crate) fn set_modifiers(&self, modifiers: Modifiers) {
+        self.modifiers.store(modifiers);
+    }
+
+    /// The last known pointer position relative to the window's top left corner in logical
+    /// pixels, as `(x, y)`. These are the same coordinates Slint uses for layout and pointer
+    /// events. During a drag this may lie outside of the window.
+    pub fn position(&self) -> (f32, f32) {
+        self.position.load()
+    }
+
+    /// The last known pointer position in physical device pixels, as `(x, y)`. This is the logical
+    /// [`position()`][Self::position()] multiplied by the window's scale factor, so on a 2x display
+    /// it maps one to one to the pixels of a buffer twice the editor's logical size. Use this for
+    /// pixel-exact tools like drawing on a spectrogram image. The values are not rounded, so a
+    /// tool can decide whether to floor or round to pick a pixel.
+    pub fn physical_position(&self) -> (f32, f32) {
+        let (x, y) = self.position.load();
+        let scale_factor = self.scale_factor.load();
+
+        (x * scale_factor, y * scale_factor)
+    }
+
+    /// Update the last known pointer position in logical pixels.
+    pub(crate) fn set_position(&self, x: f32, y: f32) {
+        self.position.store((x, y));
+    }
+
+    /// Update the scale factor used by [`physical_position()`][Self::physical_position()].
+    pub(crate) fn set_scale_factor(&self, scale_factor: f32) {
+        self.scale_factor.store(scale_factor);
+    }
+
+    /// Take the number of whole scroll wheel steps, or "clicks", since the last call as `(x, y)`.
+    /// Slint reports scrolling in pixels, which makes it awkward to implement stepped controls like
+    /// enum selectors. Calling this from a `TouchArea`'s `scroll-event` handler instead gives you
+    /// one step per wheel click. Smooth scrolling from trackpads is accumulated until it adds up
+    /// to a whole step, and the remainder is kept for the next call.
+    pub fn take_scroll_steps(&self) -> (i32, i32) {
+        let (x, y) = self.scroll_lines.load();
+        let (steps_x, steps_y) = (x.trunc(), y.trunc());
+        self.scroll_lines.store((x - steps_x, y - steps_y));
+
+        (steps_x as i32, steps_y as i32)
+    }
+
+    /// The most recent scroll event in both pixels and wheel steps, or `None` if nothing has been
+    /// scrolled yet. Called from a `TouchArea`'s `scroll-event` handler, this describes the event
+    /// being handled. Unlike [`take_scroll_steps()`][Self::take_scroll_steps()] this doesn't
+    /// accumulate anything, so it can be called any number of times.
+    pub fn last_scroll(&self) -> Option<SlintScrollDelta> {
+        self.last_scroll.load()
+    }
+
+    /// Record a scroll event and add its movement to the accumulated scroll steps. Whole steps
+    /// from earlier events that were never taken are discarded so they don't cause a sudden jump
+    /// when a stepped control is scrolled later.
+    pub(crate) fn add_scroll(&self, delta: SlintScrollDelta) {
+        let (old_x, old_y) = self.scroll_lines.load();
+        let (x, y) = delta.steps;
+        self.scroll_lines.store((old_x.fract() + x, old_y.fract() + y));
+        self.last_scroll.store(Some(delta));
+    }
+
     /// Take and clear any pending request.
     pub(crate) fn take_request(&self) -> Option<(bool, bool)> {
         self.request.swap(None)
     }
 }
 
+/// The movement of a single scroll event, both in pixels and in wheel steps. See
+/// [`SlintMouseControl::last_scroll()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlintScrollDelta {
+    /// The movement in logical pixels as `(x, y)`. This is the same delta Slint's `scroll-event`
+    /// receives.
+    pub pixels: (f32, f32),
+    /// The movement in wheel steps as `(x, y)`. A click of a notched wheel is one step, and
+    /// smooth scrolling results in fractional steps.
+    pub steps: (f32, f32),
+    /// Whether the event came from a device that scrolls in discrete lines, like a notched mouse
+    /// wheel, rather than a trackpad or a free-spinning wheel that scrolls in pixels.
+    pub discrete: bool,
+}
+
+/// Initialize the Slint platform and load the editor's fonts ahead of time. Both otherwise happen
+/// the first time an editor is opened, which adds to the latency of that first open. Each only
+/// needs to happen once, so calling this more than once or before opening an editor is harmless.
+///
+/// The fonts in `assets` are registered like the ones from [`SlintEditorOptions::assets`] are
+/// when an editor opens, so pass the same directory. Fonts are shared by the whole process, and
+/// the ones that were already registered are skipped. This requires the `fonts` feature.
+///
+/// Slint's platform state is tied to the thread it was initialized on, so this should be called
+/// from the GUI thread. [`Plugin::editor()`][nih_plug::prelude::Plugin::editor()] is a good place
+/// for this. On platforms where baseview runs the editor's window on a thread of its own, like on
+/// Linux, that thread's platform is still set up when the editor opens, so only the fonts are
+/// loaded ahead of time there.
+///
+/// Returns an error if the platform could not be set up, in which case no editor can be opened.
+pub fn prewarm_slint_platform(
+    assets: Option<&SlintAssetDirectory>,
+) -> Result<(), SlintEditorError> {
+    platform::ensure_slint_platform()?;
+    if let Some(assets) = assets {
+        assets.register_fonts();
+    }
+
+    Ok(())
+}
+
 /// Create an [`Editor`] instance using a [Slint](https://slint.dev/) GUI. The [`SlintState`]
 /// passed to this function contains the GUI's initial size, and this is kept in sync whenever
 /// the GUI gets resized. You can also use this to know if the GUI is open, so you can avoid
@@ -111,6 +380,11 @@ impl SlintMouseControl {
 /// [`SlintMouseControl`] for controlling cursor behavior during drag operations. The factory
 /// is called each time the editor window is opened.
 ///
+/// When the editor closes, the component's window receives a close request before it's destroyed.
+/// Components can use `slint::Window::on_close_requested()` to save drafts or stop background
+/// work. Refusing the request with `CloseRequestResponse::KeepWindowShown` is not supported since
+/// the host decides when the editor closes.
+///
 /// See [`SlintState::from_size()`].
 ///
 /// # Example
@@ -166,7 +440,7 @@ pub fn create_slint_editor<C, F>(
     component_factory: F,
 ) -> Option<Box<dyn Editor>>
 where
-    C: slint::ComponentHandle + 'static,
+    C: SlintComponent,
     F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
 {
     create_slint_editor_with_param_callback(slint_state, component_factory, None)
@@ -178,6 +452,11 @@ where
 /// The callback receives a reference to the Slint component, allowing you to update
 /// the UI to reflect the new parameter values.
 ///
+/// Changes are coalesced, so the callback runs at most once per frame on the GUI thread no matter
+/// how many parameters the host changed in between. Bulk updates that span several frames can
+/// hold the callback back until they're done with
+/// [`SlintState::with_suppressed_param_callbacks()`].
+///
 /// # Example
 ///
 /// ```ignore
@@ -203,16 +482,84 @@ pub fn create_slint_editor_with_param_callback<C, F>(
     on_param_values_changed: Option<editor::ParamChangedCallback<C>>,
 ) -> Option<Box<dyn Editor>>
 where
-    C: slint::ComponentHandle + 'static,
+    C: SlintComponent,
+    F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
+{
+    create_slint_editor_with_options(
+        slint_state,
+        component_factory,
+        on_param_values_changed,
+        SlintEditorOptions::default(),
+    )
+}
+
+/// Like [`create_slint_editor_with_param_callback`], but with additional [`SlintEditorOptions`]
+/// for fine-tuning the editor's behavior.
+pub fn create_slint_editor_with_options<C, F>(
+    slint_state: Arc<SlintState>,
+    component_factory: F,
+    on_param_values_changed: Option<editor::ParamChangedCallback<C>>,
+    options: SlintEditorOptions,
+) -> Option<Box<dyn Editor>>
+where
+    C: SlintComponent,
+    F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
+{
+    new_slint_editor(
+        slint_state,
+        component_factory,
+        on_param_values_changed,
+        options,
+        None,
+    )
+}
+
+/// Like [`create_slint_editor_with_options`], but the editor's frames are passed to
+/// `render_target` instead of being drawn to its window. This is meant for integrations that
+/// composite the GUI themselves. See [`SlintRenderTarget`] for the frame format and threading
+/// requirements.
+pub fn create_slint_editor_with_render_target<C, F>(
+    slint_state: Arc<SlintState>,
+    component_factory: F,
+    on_param_values_changed: Option<editor::ParamChangedCallback<C>>,
+    options: SlintEditorOptions,
+    render_target: Arc<dyn SlintRenderTarget>,
+) -> Option<Box<dyn Editor>>
+where
+    C: SlintComponent,
+    F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
+{
+    new_slint_editor(
+        slint_state,
+        component_factory,
+        on_param_values_changed,
+        options,
+        Some(render_target),
+    )
+}
+
+fn new_slint_editor<C, F>(
+    slint_state: Arc<SlintState>,
+    component_factory: F,
+    on_param_values_changed: Option<editor::ParamChangedCallback<C>>,
+    options: SlintEditorOptions,
+    render_target: Option<Arc<dyn SlintRenderTarget>>,
+) -> Option<Box<dyn Editor>>
+where
+    C: SlintComponent,
     F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
 {
     Some(Box::new(editor::SlintEditor {
         slint_state,
         component_factory: Arc::new(component_factory),
+        options,
+        render_target,
 
-        // TODO: We can't get the size of the window when baseview does its own scaling, so if the
-        //       host does not set a scale factor on Windows or Linux we should just use a factor of
-        //       1. That may make the GUI tiny but it also prevents it from getting cut off.
+        // NIH-plug's wrappers report the editor's size to the host multiplied by the host's scale
+        // factor, which is 1 until the host sets one. On Windows and Linux the window is then also
+        // opened at a scale factor of 1, since a window scaled to the system's scale factor would
+        // be larger than the space the host made for it and get cut off. That may make the GUI
+        // tiny, but the first frame is drawn at the scale the window actually has.
         #[cfg(target_os = "macos")]
         scaling_factor: AtomicCell::new(None),
         #[cfg(not(target_os = "macos"))]
@@ -220,10 +567,19 @@ where
 
         on_param_values_changed,
         emit_parameters_changed_event: Arc::new(AtomicBool::new(false)),
+        spawned_windows: Arc::default(),
     }))
 }
 
 /// State for a `nih_plug_slint` editor.
+///
+/// # Component callbacks
+///
+/// Methods like [`update_component()`][Self::update_component()] and
+/// [`set_on_frame()`][Self::set_on_frame()] take closures that receive the editor's Slint
+/// component as a `&C`. `C` must be the component type returned by the component factory, since
+/// this state is not generic over it. Closures for any other type are never called, and
+/// [`enable_ui_state()`][Self::enable_ui_state()] neither saves nor restores other types.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SlintState {
     /// The window's size in logical pixels before applying `user_scale_factor`.
@@ -236,15 +592,361 @@ pub struct SlintState {
     #[serde(with = "nih_plug::params::persist::serialize_atomic_cell")]
     user_scale_factor: AtomicCell<f64>,
 
+    /// A multiplier for text and other content the component chooses to scale, without changing
+    /// the window's size. See [`set_content_scale()`][Self::set_content_scale()]. States saved
+    /// before this existed default to 1.0.
+    #[serde(
+        with = "nih_plug::params::persist::serialize_atomic_cell",
+        default = "default_content_scale"
+    )]
+    content_scale: AtomicCell<f64>,
+
+    /// Set when [`content_scale`][Self::content_scale] changed and the component hasn't been told
+    /// yet.
+    #[serde(skip)]
+    content_scale_changed: AtomicBool,
+
+    /// The component's UI-only state, as saved by [`SlintUiState::save_ui_state()`]. See
+    /// [`enable_ui_state()`][Self::enable_ui_state()].
+    #[serde(default)]
+    extra: Mutex<Option<String>>,
+
+    /// Set when [`extra`][Self::extra] was replaced and the open component hasn't restored it
+    /// yet.
+    #[serde(skip)]
+    extra_changed: AtomicBool,
+
+    /// Saves and restores the component's UI-only state, if enabled through
+    /// [`enable_ui_state()`][Self::enable_ui_state()].
+    #[serde(skip)]
+    ui_state_handler: UiStateHandler,
+
     /// Whether the editor's window is currently open.
     #[serde(skip)]
     open: AtomicBool,
+
+    /// Set by [`request_rebuild()`][Self::request_rebuild()] and checked by the window handler
+    /// on the next frame.
+    #[serde(skip)]
+    rebuild_requested: AtomicBool,
+
+    /// Set by [`invalidate()`][Self::invalidate()] and checked by the window handler on the next
+    /// frame.
+    #[serde(skip)]
+    invalidate_requested: AtomicBool,
+
+    /// Set by [`suspend()`][Self::suspend()] and cleared by [`resume()`][Self::resume()]. The
+    /// window handler skips all work while this is set.
+    #[serde(skip)]
+    suspended: AtomicBool,
+
+    /// Whether the editor's window has keyboard focus. Updated by the window handler from
+    /// baseview's focus events.
+    #[serde(skip)]
+    focused: AtomicBool,
+
+    /// Whether the system clipboard held text when it was last checked. See
+    /// [`clipboard_has_text()`][Self::clipboard_has_text()].
+    #[serde(skip)]
+    clipboard_has_text: AtomicBool,
+
+    /// Set when the clipboard should be checked again on the next frame.
+    #[serde(skip)]
+    clipboard_check_requested: AtomicBool,
+
+    /// Called with the component and whether the clipboard holds text. Set through
+    /// [`set_on_clipboard_changed()`][Self::set_on_clipboard_changed()].
+    #[serde(skip)]
+    on_clipboard_changed: ComponentCallback<bool>,
+
+    /// The reduced motion preference last passed to the component, or `None` if it hasn't been
+    /// checked yet. See [`set_on_reduced_motion_changed()`][Self::set_on_reduced_motion_changed()].
+    #[serde(skip)]
+    reduced_motion: AtomicCell<Option<bool>>,
+
+    /// Set when the system's reduced motion preference should be checked again on the next frame.
+    #[serde(skip)]
+    reduced_motion_check_requested: AtomicBool,
+
+    /// The value of [`accessibility::reduced_motion_override_changes()`] when the reduced motion
+    /// preference was last checked.
+    #[serde(skip)]
+    reduced_motion_override_changes: AtomicUsize,
+
+    /// Called with the component and whether the user prefers reduced motion. Set through
+    /// [`set_on_reduced_motion_changed()`][Self::set_on_reduced_motion_changed()].
+    #[serde(skip)]
+    on_reduced_motion_changed: ComponentCallback<bool>,
+
+    /// Whether the window handler expects upcoming frames to change what's on screen. See
+    /// [`needs_redraw()`][Self::needs_redraw()].
+    #[serde(skip)]
+    needs_redraw: AtomicBool,
+
+    /// The number of [`with_suppressed_param_callbacks()`][Self::with_suppressed_param_callbacks()]
+    /// calls that are currently running.
+    #[serde(skip)]
+    param_callback_suppressions: AtomicUsize,
+
+    /// Updates queued through [`update_component()`][Self::update_component()]. These are run by
+    /// the window handler on the next frame.
+    #[serde(skip)]
+    component_updates: ComponentUpdateQueue,
+
+    /// Callbacks waiting for a copy of the next presented frame. Queued through
+    /// [`request_snapshot()`][Self::request_snapshot()].
+    #[serde(skip)]
+    snapshot_requests: SnapshotQueue,
+
+    /// Callbacks waiting to access the window on the next frame. Queued through
+    /// [`with_window()`][Self::with_window()].
+    #[serde(skip)]
+    window_requests: WindowRequestQueue,
+
+    /// Called with the new open state whenever the editor opens or closes. Set through
+    /// [`set_on_open_changed()`][Self::set_on_open_changed()].
+    #[serde(skip)]
+    on_open_changed: Callback<dyn Fn(bool) + Send + Sync>,
+
+    /// Invoked with the physical size of the window's drawing surface when it's created or
+    /// resized. Set through [`set_on_surface_changed()`][Self::set_on_surface_changed()].
+    #[serde(skip)]
+    on_surface_changed: Callback<dyn Fn(u32, u32) + Send + Sync>,
+
+    /// Drawn on top of every frame. Set through [`set_overlay()`][Self::set_overlay()].
+    #[serde(skip)]
+    overlay: Callback<dyn Fn(&mut SlintOverlayFrame) + Send + Sync>,
+
+    /// Called with the component whenever the held modifier keys change. Set through
+    /// [`set_on_modifiers_changed()`][Self::set_on_modifiers_changed()].
+    #[serde(skip)]
+    on_modifiers_changed: ComponentCallback<Modifiers>,
+
+    /// Called with the component and the frame's delta time at the start of every frame. Set
+    /// through [`set_on_frame()`][Self::set_on_frame()].
+    #[serde(skip)]
+    on_frame: ComponentCallback<f32>,
+
+    /// Called with the component and the content scale when the component is created and
+    /// whenever the content scale changes. Set through
+    /// [`set_on_content_scale_changed()`][Self::set_on_content_scale_changed()].
+    #[serde(skip)]
+    on_content_scale_changed: ComponentCallback<f32>,
+
+    /// The user scale factors offered by [`next_scale()`][Self::next_scale()] and
+    /// [`prev_scale()`][Self::prev_scale()], in ascending order.
+    #[serde(skip)]
+    scale_presets: Mutex<Vec<f64>>,
+
+    /// The increments the window's logical size snaps to when it's resized, as `(width, height)`.
+    /// See [`set_resize_step()`][Self::set_resize_step()].
+    #[serde(skip)]
+    resize_step: AtomicCell<(u32, u32)>,
+
+    /// A logical size before applying `user_scale_factor` that the window should be resized to.
+    /// Set through [`request_resize()`][Self::request_resize()] and applied on the GUI thread.
+    #[serde(skip)]
+    requested_size: AtomicCell<Option<(u32, u32)>>,
+
+    /// The scale factors and sizes of the open editor. See [`scale_info()`][Self::scale_info()].
+    #[serde(skip)]
+    scale_info: AtomicCell<Option<SlintScaleInfo>>,
+
+    /// The part of the window that changed in the last presented frame. See
+    /// [`last_dirty_region()`][Self::last_dirty_region()].
+    #[serde(skip)]
+    last_dirty_region: AtomicCell<Option<SlintDirtyRegion>>,
+
+    /// How the open editor renders. See [`diagnostics()`][Self::diagnostics()].
+    #[serde(skip)]
+    render_mode: AtomicCell<Option<SlintRenderMode>>,
+
+    /// The window opacity set through [`set_window_opacity()`][Self::set_window_opacity()].
+    #[serde(skip)]
+    window_opacity: AtomicCell<f32>,
+
+    /// Set when the window opacity changed and the window handler needs to apply it.
+    #[serde(skip)]
+    window_opacity_changed: AtomicBool,
+
+    /// Queries the component's preferred size for [`fit_to_content()`][Self::fit_to_content()].
+    #[serde(skip)]
+    content_fit: ContentFitRequest,
+
+    /// Editor-wide keyboard shortcuts. See [`add_shortcut()`][Self::add_shortcut()].
+    #[serde(skip)]
+    shortcuts: ShortcutTable,
+
+    /// The last error that occurred while opening or running the editor. See
+    /// [`last_error()`][Self::last_error()].
+    #[serde(skip)]
+    last_error: Mutex<Option<SlintEditorError>>,
+}
+
+/// The scale factors and sizes the editor is currently rendering with, for diagnosing HiDPI
+/// issues. See [`SlintState::scale_info()`]. All sizes are `(width, height)` pairs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlintScaleInfo {
+    /// The DPI scale factor of the window, as reported by baseview or chosen through the
+    /// [`ScalePolicy`].
+    pub scale_factor: f32,
+    /// Where the DPI scale factor came from.
+    pub scale_source: SlintScaleSource,
+    /// The user scale factor applied on top of the DPI scale factor. See
+    /// [`SlintState::user_scale_factor()`].
+    pub user_scale_factor: f64,
+    /// The render scale from [`SlintEditorOptions::render_scale`] after replacing invalid values.
+    pub render_scale: f32,
+    /// The scale factor Slint itself renders with. This is the DPI scale factor multiplied by the
+    /// render scale.
+    pub slint_scale_factor: f32,
+    /// The window's size in logical pixels.
+    pub logical_size: (u32, u32),
+    /// The window's size in physical pixels, which is also the size of the drawing surface.
+    pub physical_size: (u32, u32),
+    /// The size of the buffer Slint renders into in physical pixels. This only differs from the
+    /// physical size when the render scale is not 1.0.
+    pub render_size: (u32, u32),
+}
+
+/// Where the editor's DPI scale factor came from. See [`SlintScaleInfo::scale_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlintScaleSource {
+    /// The host set the scale factor explicitly before the editor opened. Hosts that do this
+    /// usually manage scaling themselves, so plugins may want to hide their own zoom controls.
+    Host,
+    /// The host didn't set a scale factor, or [`ScalePolicy::System`] ignores it, so the system's
+    /// scale factor for the window is used.
+    System,
+    /// The scale factor was fixed through [`ScalePolicy::Fixed`] or the
+    /// `NIH_PLUG_SLINT_FORCE_SCALE` environment variable.
+    Fixed,
+}
+
+/// The part of the window that changed in a frame, in physical pixels relative to the window's
+/// top-left corner. See [`SlintState::last_dirty_region()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlintDirtyRegion {
+    /// The left edge of the region.
+    pub x: u32,
+    /// The top edge of the region.
+    pub y: u32,
+    /// The region's width. This is zero if nothing changed.
+    pub width: u32,
+    /// The region's height. This is zero if nothing changed.
+    pub height: u32,
+}
+
+impl SlintDirtyRegion {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+/// The user scale factors offered by default, for a typical 75% to 200% zoom menu.
+pub const DEFAULT_SCALE_PRESETS: [f64; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// A pending request to resize the window to the component's preferred logical size.
+#[derive(Default)]
+struct ContentFitRequest(Mutex<Option<Box<dyn FnOnce(&dyn Any) -> Option<(f32, f32)> + Send>>>);
+
+impl std::fmt::Debug for ContentFitRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ContentFitRequest")
+            .field(&self.0.lock().is_some())
+            .finish()
+    }
+}
+
+/// Registered keyboard shortcuts and their callbacks.
+#[derive(Default)]
+struct ShortcutTable(Mutex<Vec<(SlintShortcut, Arc<dyn Fn() + Send + Sync>)>>);
+
+impl std::fmt::Debug for ShortcutTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.lock().iter().map(|(shortcut, _)| shortcut.clone()))
+            .finish()
+    }
+}
+
+/// Functions that save and restore the UI-only state of the component, which is passed as a
+/// `&dyn Any`. See [`SlintState::enable_ui_state()`].
+#[derive(Default)]
+struct UiStateHandler(Mutex<Option<Arc<UiStateFunctions>>>);
+
+/// The functions stored in a [`UiStateHandler`].
+struct UiStateFunctions {
+    save: Box<dyn Fn(&dyn Any) -> Option<String> + Send + Sync>,
+    restore: Box<dyn Fn(&dyn Any, &str) + Send + Sync>,
+}
+
+impl std::fmt::Debug for UiStateHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UiStateHandler")
+            .field(&self.0.lock().is_some())
+            .finish()
+    }
+}
+
+/// The content scale for new and previously saved states.
+fn default_content_scale() -> AtomicCell<f64> {
+    AtomicCell::new(1.0)
+}
+
+/// A queue of closures that should be run on the editor's Slint component. The component is
+/// passed as a `&dyn Any` since [`SlintState`] is not generic over the component type.
+#[derive(Default)]
+struct ComponentUpdateQueue(Mutex<Vec<Box<dyn FnOnce(&dyn Any) + Send>>>);
+
+impl std::fmt::Debug for ComponentUpdateQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ComponentUpdateQueue")
+            .field(&self.0.lock().len())
+            .finish()
+    }
+}
+
+/// Callbacks that receive a copy of the next frame shown on screen.
+#[derive(Default)]
+struct SnapshotQueue(Mutex<Vec<Box<dyn FnOnce(SharedPixelBuffer<Rgb8Pixel>) + Send>>>);
+
+impl std::fmt::Debug for SnapshotQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SnapshotQueue")
+            .field(&self.0.lock().len())
+            .finish()
+    }
+}
+
+/// Callbacks that access the editor's window on the next frame.
+#[derive(Default)]
+struct WindowRequestQueue(Mutex<Vec<Box<dyn FnOnce(&mut SlintWindowAccess) + Send>>>);
+
+impl std::fmt::Debug for WindowRequestQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("WindowRequestQueue")
+            .field(&self.0.lock().len())
+            .finish()
+    }
+}
+
+impl ComponentUpdateQueue {
+    /// Take all queued updates, leaving the queue empty.
+    fn take(&self) -> Vec<Box<dyn FnOnce(&dyn Any) + Send>> {
+        std::mem::take(&mut *self.0.lock())
+    }
 }
 
 impl<'a> PersistentField<'a, SlintState> for Arc<SlintState> {
     fn set(&self, new_value: SlintState) {
         self.size.store(new_value.size.load());
         self.user_scale_factor.store(new_value.user_scale_factor.load());
+        self.set_content_scale(new_value.content_scale.load());
+        if let Some(state) = new_value.extra.lock().take() {
+            self.set_ui_state(state);
+        }
     }
 
     fn map<F, R>(&self, f: F) -> R
@@ -259,11 +961,7 @@ impl SlintState {
     /// Initialize the GUI's state. This value can be passed to [`create_slint_editor()`]. The window
     /// size is in logical pixels, so before it is multiplied by the DPI scaling factor.
     pub fn from_size(width: u32, height: u32) -> Arc<SlintState> {
-        Arc::new(SlintState {
-            size: AtomicCell::new((width, height)),
-            user_scale_factor: AtomicCell::new(1.0),
-            open: AtomicBool::new(false),
-        })
+        Self::from_size_with_scale(width, height, 1.0)
     }
 
     /// Initialize the GUI's state with a custom initial user scale factor.
@@ -272,7 +970,45 @@ impl SlintState {
         Arc::new(SlintState {
             size: AtomicCell::new((width, height)),
             user_scale_factor: AtomicCell::new(user_scale_factor),
+            content_scale: default_content_scale(),
+            content_scale_changed: AtomicBool::new(false),
+            extra: Mutex::new(None),
+            extra_changed: AtomicBool::new(false),
+            ui_state_handler: UiStateHandler::default(),
             open: AtomicBool::new(false),
+            rebuild_requested: AtomicBool::new(false),
+            invalidate_requested: AtomicBool::new(false),
+            suspended: AtomicBool::new(false),
+            focused: AtomicBool::new(false),
+            clipboard_has_text: AtomicBool::new(false),
+            clipboard_check_requested: AtomicBool::new(false),
+            on_clipboard_changed: Callback::default(),
+            reduced_motion: AtomicCell::new(None),
+            reduced_motion_check_requested: AtomicBool::new(false),
+            reduced_motion_override_changes: AtomicUsize::new(0),
+            on_reduced_motion_changed: Callback::default(),
+            needs_redraw: AtomicBool::new(true),
+            param_callback_suppressions: AtomicUsize::new(0),
+            component_updates: ComponentUpdateQueue::default(),
+            snapshot_requests: SnapshotQueue::default(),
+            window_requests: WindowRequestQueue::default(),
+            on_open_changed: Callback::default(),
+            on_surface_changed: Callback::default(),
+            overlay: Callback::default(),
+            on_modifiers_changed: Callback::default(),
+            on_frame: Callback::default(),
+            on_content_scale_changed: Callback::default(),
+            scale_presets: Mutex::new(DEFAULT_SCALE_PRESETS.to_vec()),
+            resize_step: AtomicCell::new((1, 1)),
+            requested_size: AtomicCell::new(None),
+            scale_info: AtomicCell::new(None),
+            last_dirty_region: AtomicCell::new(None),
+            render_mode: AtomicCell::new(None),
+            window_opacity: AtomicCell::new(1.0),
+            window_opacity_changed: AtomicBool::new(false),
+            content_fit: ContentFitRequest::default(),
+            shortcuts: ShortcutTable::default(),
+            last_error: Mutex::new(None),
         })
     }
 
@@ -310,34 +1046,1408 @@ impl SlintState {
         self.user_scale_factor.store(scale);
     }
 
-    /// Whether the GUI is currently visible.
-    // Called `is_open()` instead of `open()` to avoid the ambiguity.
-    pub fn is_open(&self) -> bool {
-        self.open.load(Ordering::Acquire)
+    /// Returns the current content scale. See [`set_content_scale()`][Self::set_content_scale()].
+    pub fn content_scale(&self) -> f64 {
+        self.content_scale.load()
     }
-}
 
-/// A helper for working with parameters in Slint callbacks. This wraps a [`GuiContext`]
-/// and provides convenient methods for parameter manipulation.
-pub struct SlintParamContext {
-    gui_context: Arc<dyn GuiContext>,
-}
+    /// Set a multiplier for text and other content, without changing the window's size or the
+    /// layout's geometry. Unlike the [user scale factor][Self::set_user_scale_factor()], which
+    /// zooms the entire GUI, this lets low-vision users enlarge labels in a window of the same
+    /// size. The crate only stores the value and passes it to the component through
+    /// [`set_on_content_scale_changed()`][Self::set_on_content_scale_changed()], so it's up to the
+    /// `.slint` design to apply it, usually by multiplying font sizes with it. Values that aren't
+    /// positive and finite are replaced by 1.0. Like the user scale factor, this is persisted if
+    /// the state is stored with `#[persist]`. Changes take effect on the next frame.
+    pub fn set_content_scale(&self, scale: f64) {
+        let scale = if scale.is_finite() && scale > 0.0 {
+            scale
+        } else {
+            1.0
+        };
 
-impl SlintParamContext {
-    /// Create a new parameter context from a [`GuiContext`].
-    pub fn new(gui_context: Arc<dyn GuiContext>) -> Self {
-        Self { gui_context }
+        self.content_scale.store(scale);
+        self.content_scale_changed.store(true, Ordering::Release);
     }
 
-    /// Create a [`ParamSetter`] for setting parameter values.
-    pub fn setter(&self) -> ParamSetter<'_> {
-        ParamSetter::new(self.gui_context.as_ref())
+    /// Replace the list of user scale factors used for zoom menus. The list is sorted, and
+    /// nonsensical values are removed. Defaults to [`DEFAULT_SCALE_PRESETS`]. An empty list
+    /// restores the defaults.
+    pub fn set_scale_presets(&self, presets: impl Into<Vec<f64>>) {
+        let mut presets: Vec<f64> = presets.into();
+        presets.retain(|scale| scale.is_finite() && *scale > 0.0);
+        presets.sort_by(f64::total_cmp);
+        presets.dedup();
+        if presets.is_empty() {
+            presets = DEFAULT_SCALE_PRESETS.to_vec();
+        }
+
+        *self.scale_presets.lock() = presets;
     }
 
-    /// Get a reference to the underlying [`GuiContext`].
-    pub fn gui_context(&self) -> &Arc<dyn GuiContext> {
-        &self.gui_context
+    /// Snap the window's logical size to multiples of `width` and `height` when it's resized, for
+    /// instance to keep pixel art aligned to a grid by only allowing multiples of its base size.
+    /// When the host or the user resizes the window to a size in between, the size is rounded to
+    /// the nearest step, with a minimum of one step, and the host is asked to resize the window to
+    /// match. Hosts that don't support resizing keep the window at the size they chose. A step of
+    /// 0 is treated as 1. Defaults to `(1, 1)`, which doesn't snap at all.
+    pub fn set_resize_step(&self, width: u32, height: u32) {
+        self.resize_step.store((width.max(1), height.max(1)));
+    }
+
+    /// The increments the window's logical size snaps to, as `(width, height)`. See
+    /// [`set_resize_step()`][Self::set_resize_step()].
+    pub fn resize_step(&self) -> (u32, u32) {
+        self.resize_step.load()
+    }
+
+    /// Resize the editor's window to `width * height` logical pixels before applying the user
+    /// scale factor, for instance to show or hide an extra panel. Returns `false` if the editor is
+    /// not open.
+    ///
+    /// The resize happens on the GUI thread during the next frame or event. The host is asked
+    /// first through [`GuiContext::request_resize()`], and the window is only resized if the host
+    /// agrees, so the host and the editor never disagree about the editor's size. If the host
+    /// declines, for instance because it doesn't support resizing plugin windows, the window keeps
+    /// its current size. [`size()`][Self::size()] reports the new size once the resize has gone
+    /// through.
+    pub fn request_resize(&self, width: u32, height: u32) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.requested_size.store(Some((width.max(1), height.max(1))));
+        true
+    }
+
+    /// The scale factors and sizes the open editor is currently rendering with, or `None` if the
+    /// editor is not open. This is meant for confirming the scaling pipeline when debugging HiDPI
+    /// issues, for instance by including it in a bug report or showing it in a debug overlay. It's
+    /// updated whenever the window is resized or its scale factor changes.
+    pub fn scale_info(&self) -> Option<SlintScaleInfo> {
+        self.scale_info.load()
+    }
+
+    /// Whether the open editor's DPI scale factor was set by the host, taken from the system, or
+    /// fixed through the options. Returns `None` if the editor is not open. Plugins can use this
+    /// to hide their own zoom controls when the host already manages scaling.
+    pub fn scale_source(&self) -> Option<SlintScaleSource> {
+        self.scale_info().map(|scale_info| scale_info.scale_source)
+    }
+
+    /// The bounding box of the part of the window that changed in the last presented frame, or
+    /// `None` if the editor is not open or hasn't presented a frame yet. Plugins can forward this
+    /// to hosts that accept damage rectangles for compositing the plugin's window. Most hosts
+    /// don't and simply redraw the entire window, but the ones that do can avoid some work.
+    ///
+    /// Frames that Slint rendered only partially report the area it redrew. Frames without
+    /// partial rendering information, like forced full repaints, frames with an overlay or the
+    /// software cursor, or any frame when the repaint buffer type is
+    /// [`NewBuffer`][slint::platform::software_renderer::RepaintBufferType::NewBuffer], report
+    /// the whole window. A frame that was presented without any changes reports an
+    /// [empty][SlintDirtyRegion::is_empty()] region.
+    pub fn last_dirty_region(&self) -> Option<SlintDirtyRegion> {
+        self.last_dirty_region.load()
+    }
+
+    /// Update the region reported by [`last_dirty_region()`][Self::last_dirty_region()].
+    pub(crate) fn set_last_dirty_region(&self, region: SlintDirtyRegion) {
+        self.last_dirty_region.store(Some(region));
+    }
+
+    /// Update the scale information reported by [`scale_info()`][Self::scale_info()].
+    pub(crate) fn set_scale_info(&self, scale_info: SlintScaleInfo) {
+        self.scale_info.store(Some(scale_info));
+    }
+
+    /// Take the size requested through [`request_resize()`][Self::request_resize()], if any.
+    pub(crate) fn take_requested_size(&self) -> Option<(u32, u32)> {
+        self.requested_size.swap(None)
+    }
+
+    /// Store the window's actual logical `(width, height)` after a resize. This is the size after
+    /// applying the user scale factor, so it's converted back to the unscaled size first.
+    pub(crate) fn set_window_size(&self, (width, height): (u32, u32)) {
+        let scale = self.user_scale_factor();
+        self.size.store((
+            (width as f64 / scale).round() as u32,
+            (height as f64 / scale).round() as u32,
+        ));
+    }
+
+    /// Snap a logical `(width, height)` window size to the nearest multiple of the resize step.
+    pub(crate) fn snap_to_resize_step(&self, (width, height): (u32, u32)) -> (u32, u32) {
+        let (step_width, step_height) = self.resize_step();
+        let snap = |value: u32, step: u32| {
+            let steps = ((value as f64 / step as f64).round() as u32).max(1);
+            steps * step
+        };
+
+        (snap(width, step_width), snap(height, step_height))
+    }
+
+    /// The list of user scale factors used for zoom menus, in ascending order.
+    pub fn scale_presets(&self) -> Vec<f64> {
+        self.scale_presets.lock().clone()
+    }
+
+    /// The index of the preset closest to the current user scale factor. Use this to show the
+    /// selected entry in a zoom menu.
+    pub fn scale_preset_index(&self) -> usize {
+        let scale = self.user_scale_factor();
+        self.scale_presets
+            .lock()
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - scale).abs().total_cmp(&(*b - scale).abs()))
+            .map(|(index, _)| index)
+            .unwrap_or(0)
     }
+
+    /// Set the user scale factor to the preset at `index`. Returns `false` if there is no such
+    /// preset. Like [`set_user_scale_factor()`][Self::set_user_scale_factor()], the choice is
+    /// persisted with the rest of the state.
+    pub fn set_scale_preset(&self, index: usize) -> bool {
+        let scale = self.scale_presets.lock().get(index).copied();
+        match scale {
+            Some(scale) => {
+                self.set_user_scale_factor(scale);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Switch to the next larger preset, stopping at the largest one. Returns the new user scale
+    /// factor.
+    pub fn next_scale(&self) -> f64 {
+        let len = self.scale_presets.lock().len();
+        self.set_scale_preset((self.scale_preset_index() + 1).min(len - 1));
+        self.user_scale_factor()
+    }
+
+    /// Switch to the next smaller preset, stopping at the smallest one. Returns the new user scale
+    /// factor.
+    pub fn prev_scale(&self) -> f64 {
+        self.set_scale_preset(self.scale_preset_index().saturating_sub(1));
+        self.user_scale_factor()
+    }
+
+    /// Whether the GUI is currently visible.
+    // Called `is_open()` instead of `open()` to avoid the ambiguity.
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Acquire)
+    }
+
+    /// Set a callback that's invoked with the new open state whenever the editor opens or closes.
+    /// The callback runs on the thread the host opens and closes the editor from, which is not the
+    /// editor's window thread on Linux, so it must not touch the component directly. When opening,
+    /// it's called from [`Editor::spawn()`] after the window has been created. When closing, it's
+    /// called when the host drops the editor's handle, right before the window is torn down. This
+    /// can be used to pause expensive work like spectrum analysis while nobody is looking at it, or
+    /// to push the open state into the component with
+    /// [`update_component()`][Self::update_component()].
+    ///
+    /// The component itself only exists while the editor is open, so a Slint property reflecting
+    /// the open state would always be `true`. Any `.slint` logic that should only run while the
+    /// editor is visible can simply assume it is.
+    pub fn set_on_open_changed(&self, callback: impl Fn(bool) + Send + Sync + 'static) {
+        self.on_open_changed.set(Arc::new(callback));
+    }
+
+    /// Set a callback that's invoked with the new `(width, height)` in physical pixels whenever the
+    /// window's drawing surface is created or resized. The callback runs on the GUI thread, right
+    /// after the surface was created when the editor opens, and after it was resized whenever the
+    /// window's physical size changes. Unlike a Slint `changed width` handler, this also fires when
+    /// only the physical size changes, for instance when the window moves to a display with a
+    /// different scale factor. Use this to rebuild state that depends on the exact pixel size, like
+    /// buffers for an [overlay][Self::set_overlay()] or a custom compositing step. It's not called
+    /// if the surface could not be created.
+    pub fn set_on_surface_changed(&self, callback: impl Fn(u32, u32) + Send + Sync + 'static) {
+        self.on_surface_changed.set(Arc::new(callback));
+    }
+
+    /// Notify the surface changed callback, if any, about the surface's new physical size.
+    pub(crate) fn notify_surface_changed(&self, width: u32, height: u32) {
+        if let Some(callback) = self.on_surface_changed.get() {
+            callback(width, height);
+        }
+    }
+
+    /// The last error that occurred while opening or running the editor, if any. This is cleared
+    /// every time the editor is opened, so after opening it this tells whether that succeeded.
+    /// The editor never panics on these errors, so this is the way to find out about them
+    /// programmatically, for instance to show a message elsewhere in the plugin.
+    pub fn last_error(&self) -> Option<SlintEditorError> {
+        self.last_error.lock().clone()
+    }
+
+    /// A snapshot of the editor's open, focus, size, scale, and rendering state together with the
+    /// last error, for troubleshooting. Its [`Display`][std::fmt::Display] implementation formats
+    /// it as a few lines of text that users can copy into a bug report, for instance from a
+    /// hidden debug menu. This can be called from any thread, whether the editor is open or not.
+    pub fn diagnostics(&self) -> SlintDiagnostics {
+        let open = self.is_open();
+        SlintDiagnostics {
+            open,
+            suspended: self.is_suspended(),
+            focused: open && self.focused.load(Ordering::Relaxed),
+            size: self.size(),
+            user_scale_factor: self.user_scale_factor(),
+            content_scale: self.content_scale(),
+            scale_info: self.scale_info(),
+            render_mode: self.render_mode.load(),
+            last_error: self.last_error(),
+        }
+    }
+
+    /// Update the focus state reported by [`diagnostics()`][Self::diagnostics()].
+    pub(crate) fn set_focused(&self, focused: bool) {
+        self.focused.store(focused, Ordering::Relaxed);
+
+        // The user may have copied something or changed their accessibility settings in another
+        // application in the meantime
+        if focused {
+            self.refresh_clipboard();
+            self.reduced_motion_check_requested
+                .store(true, Ordering::Release);
+        }
+    }
+
+    /// Update the rendering information reported by [`diagnostics()`][Self::diagnostics()].
+    pub(crate) fn set_render_mode(&self, render_mode: SlintRenderMode) {
+        self.render_mode.store(Some(render_mode));
+    }
+
+    /// Log `error` and store it as the last error.
+    pub(crate) fn report_error(&self, error: SlintEditorError) {
+        error_log!("{}", error);
+        *self.last_error.lock() = Some(error);
+    }
+
+    /// Clear the last error. Called when the editor is opened.
+    pub(crate) fn clear_last_error(&self) {
+        *self.last_error.lock() = None;
+    }
+
+    /// Update the open state and notify the open changed callback if the state changed.
+    pub(crate) fn set_open(&self, open: bool) {
+        let was_open = self.open.swap(open, Ordering::AcqRel);
+        if !open {
+            self.scale_info.store(None);
+            self.last_dirty_region.store(None);
+            self.render_mode.store(None);
+            self.focused.store(false, Ordering::Relaxed);
+        }
+        // Spawning the editor again while it's still open replaces the window without closing it
+        if was_open == open {
+            return;
+        }
+
+        if let Some(callback) = self.on_open_changed.get() {
+            callback(open);
+        }
+    }
+
+    /// Rebuild the Slint component in place by running the component factory again, without
+    /// closing the editor's window. The window, its size, and its scale factor are kept as is, and
+    /// the parameter changed callback is invoked on the new component so it starts out in sync.
+    /// This can be used to switch between skins or layouts, or between entirely different
+    /// components as described for [`SlintComponent`]. The rebuild happens on the next frame, and
+    /// this does nothing while the editor is closed.
+    pub fn request_rebuild(&self) {
+        if self.is_open() {
+            self.rebuild_requested.store(true, Ordering::Release);
+        }
+    }
+
+    /// Run `f` without invoking the parameter changed callback for the changes it makes, and invoke
+    /// it once afterwards instead. Use this when applying a preset or another bulk update that
+    /// sets many parameters over more than one frame, for instance from a background thread, so
+    /// the component isn't updated with a half-applied state in between. Changes made within a
+    /// single GUI callback are already coalesced into one invocation, see
+    /// [`create_slint_editor_with_param_callback()`].
+    ///
+    /// The callback runs on the GUI thread's next frame after `f` returns, or after the outermost
+    /// call returns if these are nested. This can be called from any thread, and it also works
+    /// when `f` panics.
+    pub fn with_suppressed_param_callbacks<R>(&self, f: impl FnOnce() -> R) -> R {
+        /// Ends the suppression when dropped, even when unwinding.
+        struct Suppression<'a>(&'a SlintState);
+
+        impl Drop for Suppression<'_> {
+            fn drop(&mut self) {
+                self.0
+                    .param_callback_suppressions
+                    .fetch_sub(1, Ordering::AcqRel);
+                self.0.set_needs_redraw(true);
+            }
+        }
+
+        self.param_callback_suppressions
+            .fetch_add(1, Ordering::AcqRel);
+        let _suppression = Suppression(self);
+
+        f()
+    }
+
+    /// Whether the parameter changed callback is held back by
+    /// [`with_suppressed_param_callbacks()`][Self::with_suppressed_param_callbacks()].
+    pub(crate) fn param_callbacks_suppressed(&self) -> bool {
+        self.param_callback_suppressions.load(Ordering::Acquire) > 0
+    }
+
+    /// Force the entire window to be repainted on the next frame. Slint tracks which parts of the
+    /// window changed and normally only repaints those, so this is never needed for changes made
+    /// through Slint properties, including large batches of changes like loading a preset. Use
+    /// this when the window's contents may be out of sync with what Slint thinks is on screen, for
+    /// instance after the host or the window system damaged the window without telling us, or if
+    /// stale regions are visible after a custom rendering step.
+    pub fn invalidate(&self) {
+        self.invalidate_requested.store(true, Ordering::Release);
+    }
+
+    /// Pause the editor while it is open but not visible, for instance when the host keeps it in
+    /// a hidden tab. While suspended the editor doesn't render, doesn't advance Slint's timers and
+    /// animations, and doesn't apply parameter changes or
+    /// [`update_component()`][Self::update_component()] calls, so the GUI uses no CPU. Anything
+    /// that happened in the meantime is applied when [`resume()`][Self::resume()] is called.
+    /// Unlike closing the editor, the component and its state are kept alive. The suspended state
+    /// is kept when the editor is closed and reopened.
+    pub fn suspend(&self) {
+        self.suspended.store(true, Ordering::Release);
+    }
+
+    /// Resume an editor paused with [`suspend()`][Self::suspend()]. The window is fully repainted
+    /// on the next frame.
+    pub fn resume(&self) {
+        if self.suspended.swap(false, Ordering::AcqRel) {
+            self.invalidate();
+        }
+    }
+
+    /// Set the opacity of the editor's entire window, from 0.0 for fully transparent to 1.0 for
+    /// fully opaque, for effects like fading the editor in when it opens or dimming it while the
+    /// plugin is bypassed. Unlike transparency within the component, this also works for the
+    /// window's background. The opacity is applied on the GUI thread during the next frame, and
+    /// it's kept when the editor is closed, so setting it before the editor opens affects the
+    /// editor from its first frame. Defaults to 1.0.
+    ///
+    /// Only macOS supports this for the child windows plugins are embedded in, so on other
+    /// platforms the window always stays fully opaque.
+    pub fn set_window_opacity(&self, opacity: f32) {
+        let opacity = if opacity.is_nan() {
+            1.0
+        } else {
+            opacity.clamp(0.0, 1.0)
+        };
+        if self.window_opacity.swap(opacity) != opacity {
+            self.window_opacity_changed.store(true, Ordering::Release);
+        }
+    }
+
+    /// The window opacity set through [`set_window_opacity()`][Self::set_window_opacity()].
+    pub fn window_opacity(&self) -> f32 {
+        self.window_opacity.load()
+    }
+
+    /// Take the window opacity if it changed since the last call.
+    pub(crate) fn take_window_opacity_change(&self) -> Option<f32> {
+        self.window_opacity_changed
+            .swap(false, Ordering::AcqRel)
+            .then(|| self.window_opacity())
+    }
+
+    /// Whether the editor is currently paused with [`suspend()`][Self::suspend()].
+    pub fn is_suspended(&self) -> bool {
+        self.suspended.load(Ordering::Acquire)
+    }
+
+    /// Whether the editor currently has anything to redraw. This is `true` while Slint is running
+    /// animations, when the last frame changed any pixels, when an overlay is set, and when there
+    /// are parameter changes, component updates, rebuilds, or repaints waiting for the next
+    /// frame. Hosts and standalone loops that drive their own frame timers can poll this to
+    /// throttle or skip frames while the editor is idle. Always `false` while the editor is
+    /// closed or [suspended][Self::suspend()].
+    pub fn needs_redraw(&self) -> bool {
+        if !self.is_open() || self.is_suspended() {
+            return false;
+        }
+
+        self.needs_redraw.load(Ordering::Acquire)
+            || self.rebuild_requested.load(Ordering::Acquire)
+            || self.invalidate_requested.load(Ordering::Acquire)
+            || !self.component_updates.0.lock().is_empty()
+    }
+
+    /// Record whether there is anything to redraw. Called by the window handler after every frame,
+    /// and by the editor when parameter values change.
+    pub(crate) fn set_needs_redraw(&self, needs_redraw: bool) {
+        self.needs_redraw.store(needs_redraw, Ordering::Release);
+    }
+
+    /// Draw `overlay` directly into every rendered frame, after Slint has rendered it and before
+    /// it's shown on screen. This is meant for cheap, fast-changing decorations like a playhead or
+    /// a crosshair that would be wasteful to route through Slint properties. See
+    /// [`SlintOverlayFrame`] for the frame's coordinate system. Replaces any previously set
+    /// overlay.
+    ///
+    /// The overlay runs on the GUI thread for every frame while the editor is open, so it should
+    /// be quick and must not block. It draws on a copy of Slint's output, so Slint's own rendering
+    /// is never affected and the overlay is redrawn from scratch each frame.
+    pub fn set_overlay(&self, overlay: impl Fn(&mut SlintOverlayFrame) + Send + Sync + 'static) {
+        self.overlay.set(Arc::new(overlay));
+    }
+
+    /// Remove the overlay set with [`set_overlay()`][Self::set_overlay()].
+    pub fn clear_overlay(&self) {
+        self.overlay.clear();
+    }
+
+    /// Get the current overlay, if any.
+    pub(crate) fn overlay(&self) -> Option<Arc<dyn Fn(&mut SlintOverlayFrame) + Send + Sync>> {
+        self.overlay.get()
+    }
+
+    /// Run `f` on the editor's Slint component from any thread. The Slint component can only be
+    /// accessed from the GUI thread, so the update is queued and it runs at the start of the next
+    /// frame. This is useful for pushing data like meter readings or analysis results from a
+    /// background task. Returns `false` and drops `f` if the editor is not open.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// self.params.editor_state.update_component(move |ui: &MyPluginUI| {
+    ///     ui.set_peak_level(peak_level);
+    /// });
+    /// ```
+    pub fn update_component<C, F>(&self, f: F) -> bool
+    where
+        C: SlintComponent,
+        F: FnOnce(&C) + Send + 'static,
+    {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.queue_component_update(Box::new(move |component: &dyn Any| {
+            if let Some(component) = component.downcast_ref::<C>() {
+                f(component);
+            }
+        }));
+
+        true
+    }
+
+    /// Queue an update for the next frame, whether or not the editor is open yet. Updates queued
+    /// while the editor is closed run once it opens again, unless they're cleared first.
+    pub(crate) fn queue_component_update(&self, update: Box<dyn FnOnce(&dyn Any) + Send>) {
+        self.component_updates.0.lock().push(update);
+    }
+
+    /// Resize the window to fit the component's content after its layout changed, for instance
+    /// after rows were added to a modulation matrix. On the next frame `preferred_size` is called
+    /// with the component to get its preferred `(width, height)` in logical pixels, and the window
+    /// is resized to that through [`request_resize()`][Self::request_resize()], so the host has to
+    /// agree to the new size. Returns `false` and drops `preferred_size` if the editor is not
+    /// open. A newer request replaces one that hasn't run yet.
+    ///
+    /// Slint doesn't expose a window's layout constraints to platform code, so the component has
+    /// to provide its preferred size itself, usually by forwarding its main layout's
+    /// `preferred-width` and `preferred-height` through output properties.
+    ///
+    /// This is best effort. The size is rounded up to whole pixels, the user scale factor is taken
+    /// into account, and nothing happens if the window already has that size. The window is only
+    /// resized once per call, so a preferred size that depends on the window's own size can't make
+    /// the window oscillate, but it may not converge on a stable size either. Prefer layouts whose
+    /// preferred size only depends on their content.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // out property <length> content-width: layout.preferred-width;
+    /// // out property <length> content-height: layout.preferred-height;
+    /// // layout := VerticalLayout { ... }
+    ///
+    /// editor_state.fit_to_content(|ui: &PluginUI| {
+    ///     (ui.get_content_width(), ui.get_content_height())
+    /// });
+    /// ```
+    pub fn fit_to_content<C, F>(&self, preferred_size: F) -> bool
+    where
+        C: SlintComponent,
+        F: FnOnce(&C) -> (f32, f32) + Send + 'static,
+    {
+        if !self.is_open() {
+            return false;
+        }
+
+        *self.content_fit.0.lock() = Some(Box::new(move |component: &dyn Any| {
+            component.downcast_ref::<C>().map(preferred_size)
+        }));
+
+        true
+    }
+
+    /// Run a pending [`fit_to_content()`][Self::fit_to_content()] request, if any.
+    pub(crate) fn run_content_fit(&self, component: &dyn Any) {
+        let Some(request) = self.content_fit.0.lock().take() else {
+            return;
+        };
+        let Some((width, height)) = request(component) else {
+            return;
+        };
+        if !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0) {
+            return;
+        }
+
+        let scale = self.user_scale_factor();
+        let size = (
+            (width as f64 / scale).ceil() as u32,
+            (height as f64 / scale).ceil() as u32,
+        );
+        if size != self.inner_logical_size() {
+            self.request_resize(size.0, size.1);
+        }
+    }
+
+    /// Call `callback` whenever `shortcut` is pressed while the editor has keyboard focus,
+    /// regardless of which control is focused. Use this for editor-wide shortcuts like saving a
+    /// preset with [`SlintShortcut::command('s')`][SlintShortcut::command()]. Matching key presses
+    /// are consumed, so they reach neither the component nor the host. All other keys are handled
+    /// as usual. Adding a shortcut that's already registered replaces its callback. Key repeats
+    /// are consumed without calling the callback again.
+    ///
+    /// The callback runs on the GUI thread. Use [`update_component()`][Self::update_component()]
+    /// from there to update the component.
+    ///
+    /// Only key presses the host passes to the editor's window can trigger shortcuts. Many hosts
+    /// keep common shortcuts like Ctrl+S for themselves.
+    pub fn add_shortcut(
+        &self,
+        shortcut: SlintShortcut,
+        callback: impl Fn() + Send + Sync + 'static,
+    ) {
+        let mut shortcuts = self.shortcuts.0.lock();
+        shortcuts.retain(|(existing, _)| *existing != shortcut);
+        shortcuts.push((shortcut, Arc::new(callback)));
+    }
+
+    /// Remove a shortcut added with [`add_shortcut()`][Self::add_shortcut()].
+    pub fn remove_shortcut(&self, shortcut: &SlintShortcut) {
+        self.shortcuts
+            .0
+            .lock()
+            .retain(|(existing, _)| existing != shortcut);
+    }
+
+    /// Run the callback of the shortcut matching `event`, if any. Returns whether the event
+    /// matched a shortcut and should be consumed.
+    pub(crate) fn handle_shortcut(&self, event: &keyboard_types::KeyboardEvent) -> bool {
+        // The callback is cloned out first so it can safely change the shortcuts
+        let callback = self
+            .shortcuts
+            .0
+            .lock()
+            .iter()
+            .find(|(shortcut, _)| shortcut.matches(event))
+            .map(|(_, callback)| callback.clone());
+
+        match callback {
+            Some(callback) => {
+                if !event.repeat {
+                    callback();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Call `f` with the editor's Slint component whenever the held modifier keys change, before
+    /// the event that changed them is passed to Slint. Use this to mirror the modifiers into
+    /// component properties so Slint code can branch on them directly, for instance to show a
+    /// fine adjustment indicator while Shift is held. The modifiers are taken from both keyboard
+    /// and mouse events. Replaces any previously set callback.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // in property <bool> shift-held;
+    /// // in property <bool> alt-held;
+    ///
+    /// self.params.editor_state.set_on_modifiers_changed(|ui: &MyPluginUI, modifiers| {
+    ///     ui.set_shift_held(modifiers.contains(Modifiers::SHIFT));
+    ///     ui.set_alt_held(modifiers.contains(Modifiers::ALT));
+    /// });
+    /// ```
+    pub fn set_on_modifiers_changed<C, F>(&self, f: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, Modifiers) + Send + Sync + 'static,
+    {
+        self.on_modifiers_changed.set_for(f);
+    }
+
+    /// Call the callback set through
+    /// [`set_on_modifiers_changed()`][Self::set_on_modifiers_changed()], if any.
+    pub(crate) fn notify_modifiers_changed(&self, component: &dyn Any, modifiers: Modifiers) {
+        self.on_modifiers_changed.call(component, modifiers);
+    }
+
+    /// Call `f` with the editor's Slint component at the start of every frame, along with the
+    /// time in seconds that passed since the previous frame. Use this for custom time-based
+    /// animation that doesn't go through Slint's animation system, like meters with simulated
+    /// ballistics, so they move at the same speed regardless of the actual frame rate. Replaces
+    /// any previously set callback.
+    ///
+    /// The delta time is `0.0` for the first frame after the editor opens or resumes from
+    /// [`suspend()`][Self::suspend()], and it's capped at 0.1 seconds so a stalled GUI thread
+    /// doesn't make animations jump. The callback is not called while the editor is suspended.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // in-out property <float> needle-velocity;
+    /// // in-out property <float> needle-position;
+    ///
+    /// self.params.editor_state.set_on_frame(|ui: &MyPluginUI, delta_time| {
+    ///     let velocity = ui.get_needle_velocity() - ui.get_needle_position() * 40.0 * delta_time;
+    ///     ui.set_needle_velocity(velocity * (1.0 - 5.0 * delta_time));
+    ///     ui.set_needle_position(ui.get_needle_position() + velocity * delta_time);
+    /// });
+    /// ```
+    pub fn set_on_frame<C, F>(&self, f: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, f32) + Send + Sync + 'static,
+    {
+        self.on_frame.set_for(f);
+    }
+
+    /// Remove the callback set through [`set_on_frame()`][Self::set_on_frame()].
+    pub fn clear_on_frame(&self) {
+        self.on_frame.clear();
+    }
+
+    /// Call the callback set through [`set_on_frame()`][Self::set_on_frame()], if any.
+    pub(crate) fn notify_frame(&self, component: &dyn Any, delta_time: f32) {
+        self.on_frame.call(component, delta_time);
+    }
+
+    /// Call `f` with the editor's Slint component and the
+    /// [content scale][Self::set_content_scale()] right after the component is created, and again
+    /// whenever the content scale changes. Use this to mirror the content scale into a component
+    /// property the design multiplies its font sizes with. Replaces any previously set callback.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // in property <float> text-scale: 1.0;
+    /// // Text { font-size: 12px * root.text-scale; }
+    ///
+    /// self.params.editor_state.set_on_content_scale_changed(|ui: &MyPluginUI, scale| {
+    ///     ui.set_text_scale(scale);
+    /// });
+    /// ```
+    pub fn set_on_content_scale_changed<C, F>(&self, f: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, f32) + Send + Sync + 'static,
+    {
+        self.on_content_scale_changed.set_for(f);
+        self.content_scale_changed.store(true, Ordering::Release);
+    }
+
+    /// Pass the content scale to the callback set through
+    /// [`set_on_content_scale_changed()`][Self::set_on_content_scale_changed()], if any. Unless
+    /// `force` is set, this only happens if the content scale changed since the last call.
+    pub(crate) fn notify_content_scale(&self, component: &dyn Any, force: bool) {
+        if !self.content_scale_changed.swap(false, Ordering::AcqRel) && !force {
+            return;
+        }
+
+        self.on_content_scale_changed
+            .call(component, self.content_scale() as f32);
+    }
+
+    /// Whether the system clipboard held text when it was last checked, for enabling a "Paste
+    /// settings" button only when there's something to paste. The clipboard is checked when the
+    /// editor opens, whenever its window gains focus, and after
+    /// [`refresh_clipboard()`][Self::refresh_clipboard()] was called. It is not polled, so this
+    /// can be out of date if the clipboard changes while the editor has focus.
+    ///
+    /// This only reports whether there is text, not whether that text can be parsed. Always
+    /// returns `false` on Linux, where the clipboard can't be read. See the [crate
+    /// documentation][crate#clipboard].
+    pub fn clipboard_has_text(&self) -> bool {
+        self.clipboard_has_text.load(Ordering::Relaxed)
+    }
+
+    /// Check the clipboard again on the GUI thread at the start of the next frame. See
+    /// [`clipboard_has_text()`][Self::clipboard_has_text()].
+    pub fn refresh_clipboard(&self) {
+        self.clipboard_check_requested
+            .store(true, Ordering::Release);
+    }
+
+    /// Call `f` with the editor's Slint component and whether the clipboard holds text right
+    /// after the component is created, and again whenever that changes. See
+    /// [`clipboard_has_text()`][Self::clipboard_has_text()] for when the clipboard is checked.
+    /// Replaces any previously set callback.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // in property <bool> can-paste;
+    /// // Button { text: "Paste settings"; enabled: root.can-paste; }
+    ///
+    /// self.params.editor_state.set_on_clipboard_changed(|ui: &MyPluginUI, has_text| {
+    ///     ui.set_can_paste(has_text);
+    /// });
+    /// ```
+    pub fn set_on_clipboard_changed<C, F>(&self, f: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, bool) + Send + Sync + 'static,
+    {
+        self.on_clipboard_changed.set_for(f);
+        self.refresh_clipboard();
+    }
+
+    /// Check the clipboard if that was requested, and pass the result to the callback set through
+    /// [`set_on_clipboard_changed()`][Self::set_on_clipboard_changed()] if it changed. With
+    /// `force` set, the clipboard is always checked and the callback is always called.
+    pub(crate) fn check_clipboard(&self, component: &dyn Any, force: bool) {
+        if !self.clipboard_check_requested.swap(false, Ordering::AcqRel) && !force {
+            return;
+        }
+
+        let has_text = clipboard::clipboard_has_text();
+        let changed = self.clipboard_has_text.swap(has_text, Ordering::Relaxed) != has_text;
+        if !changed && !force {
+            return;
+        }
+
+        self.on_clipboard_changed.call(component, has_text);
+    }
+
+    /// Call `f` with the editor's Slint component and whether the user
+    /// [prefers reduced motion][crate::prefers_reduced_motion()] right after the component is
+    /// created, and again whenever that changes. Changes made through
+    /// [`set_reduced_motion()`][crate::set_reduced_motion()] are picked up on the next frame. The
+    /// system setting is checked again whenever the editor's window gains focus. Replaces any
+    /// previously set callback.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // in property <bool> reduced-motion;
+    /// // animate value { duration: root.reduced-motion ? 0ms : 150ms; }
+    ///
+    /// self.params.editor_state.set_on_reduced_motion_changed(|ui: &MyPluginUI, reduced_motion| {
+    ///     ui.set_reduced_motion(reduced_motion);
+    /// });
+    /// ```
+    pub fn set_on_reduced_motion_changed<C, F>(&self, f: F)
+    where
+        C: SlintComponent,
+        F: Fn(&C, bool) + Send + Sync + 'static,
+    {
+        self.on_reduced_motion_changed.set_for(f);
+
+        // The new callback hasn't seen the current value yet
+        self.reduced_motion.store(None);
+        self.reduced_motion_check_requested
+            .store(true, Ordering::Release);
+    }
+
+    /// Check the reduced motion preference if that was requested or the override changed, and
+    /// pass it to the callback set through
+    /// [`set_on_reduced_motion_changed()`][Self::set_on_reduced_motion_changed()] if it changed.
+    /// With `force` set, the preference is always checked and the callback is always called.
+    pub(crate) fn notify_reduced_motion(&self, component: &dyn Any, force: bool) {
+        let override_changes = accessibility::reduced_motion_override_changes();
+        let override_changed = self
+            .reduced_motion_override_changes
+            .swap(override_changes, Ordering::AcqRel)
+            != override_changes;
+        let check_requested = self
+            .reduced_motion_check_requested
+            .swap(false, Ordering::AcqRel);
+        if !override_changed && !check_requested && !force {
+            return;
+        }
+
+        let reduced_motion = accessibility::prefers_reduced_motion();
+        let changed = self.reduced_motion.swap(Some(reduced_motion)) != Some(reduced_motion);
+        if !changed && !force {
+            return;
+        }
+
+        self.on_reduced_motion_changed
+            .call(component, reduced_motion);
+    }
+
+    /// Save and restore the UI-only state of the component through its [`SlintUiState`]
+    /// implementation. The state is saved whenever the component is about to be dropped and
+    /// stored in this `SlintState`, so it's persisted along with the rest of the editor's state.
+    /// It's restored right after the component is created. Call this before the editor opens.
+    pub fn enable_ui_state<C: SlintUiState>(&self) {
+        *self.ui_state_handler.0.lock() = Some(Arc::new(UiStateFunctions {
+            save: Box::new(|component| {
+                component
+                    .downcast_ref::<C>()
+                    .map(|component| component.save_ui_state())
+            }),
+            restore: Box::new(|component, state| {
+                if let Some(component) = component.downcast_ref::<C>() {
+                    component.restore_ui_state(state);
+                }
+            }),
+        }));
+    }
+
+    /// The UI-only state saved the last time the component was dropped, or set through
+    /// [`set_ui_state()`][Self::set_ui_state()]. While the editor is open, the component's current
+    /// state may differ from this. See [`enable_ui_state()`][Self::enable_ui_state()].
+    pub fn ui_state(&self) -> Option<String> {
+        self.extra.lock().clone()
+    }
+
+    /// Replace the saved UI-only state. If the editor is open, then the component restores it on
+    /// the next frame, otherwise this happens when the component is created. See
+    /// [`enable_ui_state()`][Self::enable_ui_state()].
+    pub fn set_ui_state(&self, state: impl Into<String>) {
+        *self.extra.lock() = Some(state.into());
+        self.extra_changed.store(true, Ordering::Release);
+    }
+
+    /// Save the component's UI-only state if [`enable_ui_state()`][Self::enable_ui_state()] was
+    /// called. Called right before the component is dropped.
+    pub(crate) fn save_ui_state(&self, component: &dyn Any) {
+        let handler = self.ui_state_handler.0.lock().clone();
+        if let Some(state) = handler.and_then(|handler| (handler.save)(component)) {
+            *self.extra.lock() = Some(state);
+        }
+    }
+
+    /// Restore the saved UI-only state into the component if
+    /// [`enable_ui_state()`][Self::enable_ui_state()] was called. Unless `force` is set, this only
+    /// happens if the state was replaced since the last call.
+    pub(crate) fn restore_ui_state(&self, component: &dyn Any, force: bool) {
+        if !self.extra_changed.swap(false, Ordering::AcqRel) && !force {
+            return;
+        }
+
+        let handler = self.ui_state_handler.0.lock().clone();
+        let state = self.extra.lock().clone();
+        if let (Some(handler), Some(state)) = (handler, state) {
+            (handler.restore)(component, &state);
+        }
+    }
+
+    /// Run all updates queued through [`update_component()`][Self::update_component()].
+    pub(crate) fn run_component_updates(&self, component: &dyn Any) {
+        for update in self.component_updates.take() {
+            update(component);
+        }
+    }
+
+    /// Drop all queued component updates, snapshot requests, and content fit requests. Called
+    /// when the editor closes so stale updates aren't applied to the next component.
+    pub(crate) fn clear_component_updates(&self) {
+        drop(self.component_updates.take());
+        drop(std::mem::take(&mut *self.snapshot_requests.0.lock()));
+        drop(std::mem::take(&mut *self.window_requests.0.lock()));
+        drop(self.content_fit.0.lock().take());
+    }
+
+    /// Run `f` with access to the open editor's window, for host integrations that need to focus
+    /// or close the window themselves. The window can only be used on the GUI thread, so `f` runs
+    /// there at the start of the next frame. See [`SlintWindowAccess`] for the available
+    /// operations and why the underlying baseview window isn't exposed directly.
+    ///
+    /// Returns `false` and drops `f` if the editor is not open. `f` is also dropped without being
+    /// called if the editor is closed before the next frame.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// editor_state.with_window(|window| {
+    ///     if !window.has_focus() {
+    ///         window.focus();
+    ///     }
+    /// });
+    /// ```
+    pub fn with_window(&self, f: impl FnOnce(&mut SlintWindowAccess) + Send + 'static) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.window_requests.0.lock().push(Box::new(f));
+        true
+    }
+
+    /// Run all callbacks queued through [`with_window()`][Self::with_window()].
+    pub(crate) fn run_window_requests(&self, window: &mut SlintWindowAccess) {
+        let requests = std::mem::take(&mut *self.window_requests.0.lock());
+        for request in requests {
+            request(window);
+        }
+    }
+
+    /// Capture the frame that's shown in the open editor, for features like sharing the current
+    /// settings as an image. On the next frame, a copy of exactly what's on screen, including
+    /// any overlay, is passed to `callback` on the GUI thread. The image has the window's size in
+    /// physical pixels, so on a 2x display it's twice the editor's logical size. Unlike
+    /// [`test_util::render_to_rgba()`](crate::test_util), this reflects the live component with
+    /// all of its current state, like hover effects and open popups.
+    ///
+    /// Returns `false` and drops `callback` if the editor is not open. The callback is also
+    /// dropped without being called if the editor is closed before the next frame, or if
+    /// nothing could be drawn to the window.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// editor_state.request_snapshot(move |pixels| {
+    ///     ui_ref.with_component(|ui| ui.set_snapshot(slint::Image::from_rgb8(pixels)));
+    /// });
+    /// ```
+    pub fn request_snapshot(
+        &self,
+        callback: impl FnOnce(SharedPixelBuffer<Rgb8Pixel>) + Send + 'static,
+    ) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.snapshot_requests.0.lock().push(Box::new(callback));
+        self.set_needs_redraw(true);
+        true
+    }
+
+    /// Take all pending snapshot requests.
+    pub(crate) fn take_snapshot_requests(
+        &self,
+    ) -> Vec<Box<dyn FnOnce(SharedPixelBuffer<Rgb8Pixel>) + Send>> {
+        std::mem::take(&mut *self.snapshot_requests.0.lock())
+    }
+}
+
+/// A helper for working with parameters in Slint callbacks. This wraps a [`GuiContext`]
+/// and provides convenient methods for parameter manipulation.
+pub struct SlintParamContext {
+    gui_context: Arc<dyn GuiContext>,
+}
+
+impl SlintParamContext {
+    /// Create a new parameter context from a [`GuiContext`].
+    pub fn new(gui_context: Arc<dyn GuiContext>) -> Self {
+        Self { gui_context }
+    }
+
+    /// Create a [`ParamSetter`] for setting parameter values.
+    pub fn setter(&self) -> ParamSetter<'_> {
+        ParamSetter::new(self.gui_context.as_ref())
+    }
+
+    /// Get a reference to the underlying [`GuiContext`].
+    pub fn gui_context(&self) -> &Arc<dyn GuiContext> {
+        &self.gui_context
+    }
+
+    /// Convert a plain value to a normalized `[0, 1]` value for `param`. This takes the
+    /// parameter's range and skew into account, so a logarithmic frequency parameter can be bound
+    /// to a linear `0..1` Slint slider without any manual math.
+    pub fn to_normalized<P: Param>(&self, param: &P, plain: P::Plain) -> f32 {
+        param.preview_normalized(plain)
+    }
+
+    /// Convert a normalized `[0, 1]` value to a plain value for `param`. This is the inverse of
+    /// [`to_normalized()`][Self::to_normalized()].
+    pub fn to_plain<P: Param>(&self, param: &P, normalized: f32) -> P::Plain {
+        param.preview_plain(normalized)
+    }
+
+    /// Get the parameter's current unmodulated normalized value. Use this to position a `0..1`
+    /// slider so it reflects the parameter's skew.
+    pub fn normalized_value<P: Param>(&self, param: &P) -> f32 {
+        param.unmodulated_normalized_value()
+    }
+
+    /// Get the parameter's current modulated normalized value, for drawing modulation rings and
+    /// similar indicators.
+    pub fn modulated_normalized_value<P: Param>(&self, param: &P) -> f32 {
+        param.modulated_normalized_value()
+    }
+
+    /// Get the plain value `param` would have if `normalized_offset` was applied to its current
+    /// unmodulated value as monophonic modulation.
+    pub fn preview_modulated<P: Param>(&self, param: &P, normalized_offset: f32) -> P::Plain {
+        param.preview_modulated(normalized_offset)
+    }
+
+    /// Format `param`'s current unmodulated value using the parameter's own value-to-string
+    /// function. This is what should be shown as the initial text in a value entry field.
+    pub fn value_to_string<P: Param>(&self, param: &P, include_unit: bool) -> String {
+        param.normalized_value_to_string(param.unmodulated_normalized_value(), include_unit)
+    }
+
+    /// A description of `param` and its current unmodulated value for screen readers, like
+    /// "Gain, -6.0 dB". Bind this to a control's `accessible-label` or `accessible-value`, or
+    /// read it out with [`announce_value()`][Self::announce_value()].
+    pub fn accessible_description<P: Param>(&self, param: &P) -> String {
+        format!("{}, {}", param.name(), self.value_to_string(param, true))
+    }
+
+    /// Have the screen reader read out `param`'s
+    /// [`accessible_description()`][Self::accessible_description()]. Call this when a control is
+    /// focused or adjusted from the keyboard. See [`announce()`] for platform support.
+    pub fn announce_value<P: Param>(&self, param: &P) {
+        announce(&self.accessible_description(param));
+    }
+
+    /// Format `param`'s current unmodulated plain value with a custom formatter instead of the
+    /// parameter's own value-to-string function. This only changes what the GUI shows, the host
+    /// keeps using the parameter's formatter, and values typed into the GUI are still parsed with
+    /// [`set_from_string()`][Self::set_from_string()]. NIH-plug's
+    /// [`formatters`][nih_plug::formatters] can be used here as well:
+    ///
+    /// ```ignore
+    /// let hz_or_khz = nih_plug::formatters::v2s_f32_hz_then_khz(1);
+    /// let label = param_context.value_to_string_with(&params.cutoff, |hz| hz_or_khz(hz));
+    /// ```
+    pub fn value_to_string_with<P: Param>(
+        &self,
+        param: &P,
+        format: impl Fn(P::Plain) -> String,
+    ) -> String {
+        format(param.preview_plain(param.unmodulated_normalized_value()))
+    }
+
+    /// Format `param`'s current unmodulated plain value with a fixed number of decimal places,
+    /// for compact numeric labels. Like [`value_to_string_with()`][Self::value_to_string_with()],
+    /// this doesn't affect how the host shows the value.
+    pub fn value_to_string_with_precision<P: Param<Plain = f32>>(
+        &self,
+        param: &P,
+        decimals: usize,
+    ) -> String {
+        self.value_to_string_with(param, |value| format!("{value:.decimals$}"))
+    }
+
+    /// Parse `string` using the parameter's string-to-value function and set `param` to the
+    /// result as a single gesture. Returns `false` without touching the parameter if the string
+    /// could not be parsed.
+    ///
+    /// This is the building block for host-style "Enter value..." editing. A typical setup opens a
+    /// `PopupWindow` with a `LineEdit` from a right-click context menu, and forwards the accepted
+    /// text back to Rust:
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // callback gain-value-entered(string);
+    /// // TouchArea {
+    /// //     pointer-event(event) => {
+    /// //         if (event.button == PointerEventButton.right && event.kind == PointerEventKind.up) {
+    /// //             value-popup.show();
+    /// //         }
+    /// //     }
+    /// // }
+    /// // value-popup := PopupWindow {
+    /// //     LineEdit {
+    /// //         text: root.gain-text;
+    /// //         accepted(text) => { root.gain-value-entered(text); value-popup.close(); }
+    /// //     }
+    /// // }
+    ///
+    /// // In the component factory:
+    /// let param_context = SlintParamContext::new(gui_context.clone());
+    /// ui.set_gain_text(param_context.value_to_string(&params.gain, false).into());
+    /// ui.on_gain_value_entered({
+    ///     let param_context = param_context.clone();
+    ///     let params = params.clone();
+    ///     move |text| {
+    ///         param_context.set_from_string(&params.gain, &text);
+    ///     }
+    /// });
+    /// ```
+    pub fn set_from_string<P: Param>(&self, param: &P, string: &str) -> bool {
+        match param.string_to_normalized_value(string) {
+            Some(normalized) => {
+                self.set_normalized(param, normalized);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move `param` by `steps` discrete steps, as a single gesture. Positive values step up and
+    /// negative values step down. The value is clamped at the ends of the parameter's range.
+    /// Returns `true` if the parameter's value changed. Continuous parameters move by 0.02 in the
+    /// normalized range per step, so one fiftieth of their range, or by their step size if that is
+    /// larger. See [`Param::next_normalized_step()`].
+    pub fn step<P: Param>(&self, param: &P, steps: i32) -> bool {
+        if is_syncing_from_host() {
+            return false;
+        }
+
+        let start = param.unmodulated_normalized_value();
+        let mut normalized = start;
+        for _ in 0..steps.unsigned_abs() {
+            let next = if steps > 0 {
+                param.next_normalized_step(normalized, false)
+            } else {
+                param.previous_normalized_step(normalized, false)
+            };
+            if next == normalized {
+                break;
+            }
+
+            normalized = next;
+        }
+
+        if normalized == start {
+            return false;
+        }
+
+        self.set_normalized(param, normalized);
+        true
+    }
+
+    /// Step `param` by the vertical scroll wheel clicks accumulated in `mouse_control`, for
+    /// stepped controls like `IntParam` spinners and `EnumParam` dropdowns. Call this from the
+    /// control's `TouchArea` `scroll-event` callback. Scrolling up increments the value, and every
+    /// change is sent as its own gesture. Returns `true` if the parameter's value changed.
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // callback mode-scrolled();
+    /// // TouchArea {
+    /// //     scroll-event(event) => { root.mode-scrolled(); accept }
+    /// // }
+    ///
+    /// // In the component factory:
+    /// ui.on_mode_scrolled({
+    ///     let param_context = param_context.clone();
+    ///     let params = params.clone();
+    ///     move || {
+    ///         param_context.scroll_step(&params.mode, &mouse_control);
+    ///     }
+    /// });
+    /// ```
+    pub fn scroll_step<P: Param>(&self, param: &P, mouse_control: &SlintMouseControl) -> bool {
+        let (_, steps) = mouse_control.take_scroll_steps();
+        self.step(param, steps)
+    }
+
+    /// Get the current unmodulated value of an integer parameter like an [`IntParam`], for
+    /// binding it to an integer Slint property such as a `SpinBox`'s or a stepped `Slider`'s
+    /// `value`. Call this from the parameter changed callback to keep the property in sync with
+    /// the host.
+    ///
+    /// ```ignore
+    /// // In the .slint file:
+    /// // in property <int> voices;
+    /// // in property <int> voices-min;
+    /// // in property <int> voices-max;
+    /// // callback voices-edited(int);
+    /// // SpinBox {
+    /// //     minimum: root.voices-min;
+    /// //     maximum: root.voices-max;
+    /// //     value: root.voices;
+    /// //     edited(value) => { root.voices-edited(value); }
+    /// // }
+    ///
+    /// // In the component factory:
+    /// let (min, max) = param_context.int_range(&params.voices);
+    /// ui.set_voices_min(min);
+    /// ui.set_voices_max(max);
+    /// ui.set_voices(param_context.int_value(&params.voices));
+    /// ui.on_voices_edited({
+    ///     let param_context = param_context.clone();
+    ///     let params = params.clone();
+    ///     move |voices| {
+    ///         param_context.set_int(&params.voices, voices);
+    ///     }
+    /// });
+    ///
+    /// // In the parameter changed callback:
+    /// ui.set_voices(param_context.int_value(&params.voices));
+    /// ```
+    ///
+    /// [`IntParam`]: nih_plug::prelude::IntParam
+    pub fn int_value<P: Param<Plain = i32>>(&self, param: &P) -> i32 {
+        param.preview_plain(param.unmodulated_normalized_value())
+    }
+
+    /// The smallest and largest values of an integer parameter, as `(min, max)`. Use these as a
+    /// `SpinBox`'s or `Slider`'s `minimum` and `maximum`. Reversed ranges are reported in
+    /// ascending order as well.
+    pub fn int_range<P: Param<Plain = i32>>(&self, param: &P) -> (i32, i32) {
+        let (start, end) = (param.preview_plain(0.0), param.preview_plain(1.0));
+        (start.min(end), start.max(end))
+    }
+
+    /// Set an integer parameter to `value` as a single gesture, clamping it to the parameter's
+    /// range first. Returns `true` if the parameter's value changed. Like
+    /// [`set_normalized()`][Self::set_normalized()], this does nothing while host-driven changes
+    /// are applied to the component.
+    pub fn set_int<P: Param<Plain = i32>>(&self, param: &P, value: i32) -> bool {
+        if is_syncing_from_host() {
+            return false;
+        }
+
+        let normalized = param.preview_normalized(value);
+        if param.preview_plain(normalized) == self.int_value(param) {
+            return false;
+        }
+
+        self.set_normalized(param, normalized);
+        true
+    }
+
+    /// Set `param` to a normalized `[0, 1]` value as a single gesture. This wraps the
+    /// begin/set/end calls needed for a one-off change, for example from a button or a combo box.
+    /// Use [`begin_gesture()`][Self::begin_gesture()] and friends for controls that are dragged.
+    ///
+    /// This does nothing while host-driven changes are applied to the component, see
+    /// [`sync_from_host()`].
+    pub fn set_normalized<P: Param>(&self, param: &P, normalized: f32) {
+        if is_syncing_from_host() {
+            return;
+        }
+
+        let setter = self.setter();
+        setter.begin_set_parameter(param);
+        setter.set_parameter_normalized(param, normalized);
+        setter.end_set_parameter(param);
+    }
+
+    /// Start a gesture for `param`, for instance when a slider is pressed. Values set with
+    /// [`set_in_gesture()`][Self::set_in_gesture()] until [`end_gesture()`][Self::end_gesture()]
+    /// is called are recorded by the host as a single automation gesture. Every call must be
+    /// matched by a call to `end_gesture()`.
+    ///
+    /// This does nothing while host-driven changes are applied to the component, see
+    /// [`sync_from_host()`].
+    pub fn begin_gesture<P: Param>(&self, param: &P) {
+        if !is_syncing_from_host() {
+            self.setter().begin_set_parameter(param);
+        }
+    }
+
+    /// Set `param` to a normalized `[0, 1]` value as part of the gesture started with
+    /// [`begin_gesture()`][Self::begin_gesture()], for instance while a slider is dragged.
+    ///
+    /// This does nothing while host-driven changes are applied to the component, see
+    /// [`sync_from_host()`].
+    pub fn set_in_gesture<P: Param>(&self, param: &P, normalized: f32) {
+        if !is_syncing_from_host() {
+            self.setter().set_parameter_normalized(param, normalized);
+        }
+    }
+
+    /// End the gesture started with [`begin_gesture()`][Self::begin_gesture()], for instance when
+    /// a slider is released.
+    ///
+    /// This does nothing while host-driven changes are applied to the component, see
+    /// [`sync_from_host()`].
+    pub fn end_gesture<P: Param>(&self, param: &P) {
+        if !is_syncing_from_host() {
+            self.setter().end_set_parameter(param);
+        }
+    }
+
+    /// Serialize the current unmodulated values of all of `params`' parameters to a plain text
+    /// blob, for "Copy settings" style actions. Bypass parameters are left out so pasting settings
+    /// never bypasses the plugin. The blob can be applied again with
+    /// [`set_params_from_string()`][Self::set_params_from_string()], also in another instance of
+    /// the same plugin. Persisted fields are not included.
+    pub fn params_to_string(&self, params: &impl Params) -> String {
+        let param_map = params.param_map();
+        format_param_values(
+            param_map
+                .iter()
+                .filter(|(_, param_ptr, _)| !is_bypass(*param_ptr))
+                // SAFETY: The pointers stay valid for as long as `params` is borrowed
+                .map(|(id, param_ptr, _)| unsafe {
+                    (id.as_str(), param_ptr.unmodulated_normalized_value())
+                }),
+        )
+    }
+
+    /// Serialize `params` like [`params_to_string()`][Self::params_to_string()] and copy the
+    /// result to the system clipboard. Returns `false` if the text could not be copied. This is
+    /// always the case on Linux, where the plugin needs to offer the text in another way, for
+    /// instance in a Slint `TextInput` the user can copy it from.
+    ///
+    /// The clipboard's text can't be read, only whether there is any. See the [crate
+    /// documentation][crate#clipboard]. A "Paste settings" action needs to get the text from
+    /// elsewhere, for instance from a Slint `TextInput` the user pastes into, and pass it to
+    /// [`set_params_from_string()`][Self::set_params_from_string()].
+    pub fn copy_params_to_clipboard(&self, params: &impl Params) -> bool {
+        let copied = clipboard::copy_text_to_clipboard(&self.params_to_string(params));
+        if !copied {
+            warn_log!("Could not copy the parameter values to the clipboard");
+        }
+
+        copied
+    }
+
+    /// Apply a blob created by [`params_to_string()`][Self::params_to_string()] to `params`.
+    /// Every parameter in the blob is set as its own gesture, and the component is refreshed
+    /// through the parameter changed callback like for any other change. Parameters that don't
+    /// exist in `params`, for instance because the blob was copied from an older version of the
+    /// plugin, are skipped, as are bypass parameters.
+    ///
+    /// Returns `false` without changing any parameters if the text is not a valid blob, so
+    /// arbitrary clipboard contents can be passed in without checking them first. Like
+    /// [`set_normalized()`][Self::set_normalized()], this does nothing while host-driven changes
+    /// are applied to the component.
+    pub fn set_params_from_string(&self, params: &impl Params, text: &str) -> bool {
+        let Some(values) = parse_param_values(text) else {
+            warn_log!("Ignoring pasted parameter values that are not in the expected format");
+            return false;
+        };
+        if is_syncing_from_host() {
+            return false;
+        }
+
+        let param_map = params.param_map();
+        for (id, normalized) in values {
+            let Some((_, param_ptr, _)) = param_map
+                .iter()
+                .find(|(param_id, param_ptr, _)| *param_id == id && !is_bypass(*param_ptr))
+            else {
+                continue;
+            };
+
+            // SAFETY: The pointers stay valid for as long as `params` is borrowed
+            unsafe {
+                self.gui_context.raw_begin_set_parameter(*param_ptr);
+                self.gui_context
+                    .raw_set_parameter_normalized(*param_ptr, normalized);
+                self.gui_context.raw_end_set_parameter(*param_ptr);
+            }
+        }
+
+        true
+    }
+}
+
+/// Whether `param_ptr` is the plugin's bypass parameter.
+fn is_bypass(param_ptr: ParamPtr) -> bool {
+    // SAFETY: Only called with pointers from a `param_map()` whose `Params` object is borrowed
+    unsafe { param_ptr.flags().contains(ParamFlags::BYPASS) }
 }
 
 impl Clone for SlintParamContext {
@@ -0,0 +1,133 @@
+//! Diagnostic logging for the editor. Plugin hosts rarely give you access to a plugin's stderr, so
+//! these messages are appended to a log file in the system's temporary directory instead.
+//!
+//! Only messages at or below the current [`LogLevel`] are written. The level defaults to
+//! [`LogLevel::Warn`], and it can be changed with the `NIH_PLUG_SLINT_LOG` environment variable
+//! (e.g. `NIH_PLUG_SLINT_LOG=debug`) or programmatically through [`set_log_level()`].
+
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// The name of the environment variable used to set the initial log level.
+const LOG_LEVEL_ENV_VAR: &str = "NIH_PLUG_SLINT_LOG";
+
+/// The name of the log file, relative to the system's temporary directory.
+const LOG_FILE_NAME: &str = "nih_plug_slint_debug.log";
+
+static LOG_LEVEL: OnceLock<AtomicU8> = OnceLock::new();
+
+/// The verbosity of `nih_plug_slint`'s diagnostic logging. Every level also includes the messages
+/// from the levels before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum LogLevel {
+    /// Don't log anything.
+    Off = 0,
+    /// Only log failures, such as panics caught in the window handler.
+    Error = 1,
+    /// Also log recoverable problems.
+    Warn = 2,
+    /// Also log editor lifecycle events like opening, closing, and resizing.
+    Info = 3,
+    /// Also log detailed setup steps and parameter change notifications.
+    Debug = 4,
+    /// Also log things that happen every frame or for every event.
+    Trace = 5,
+}
+
+impl LogLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => LogLevel::Off,
+            1 => LogLevel::Error,
+            2 => LogLevel::Warn,
+            3 => LogLevel::Info,
+            4 => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Some(LogLevel::Off),
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+}
+
+/// Set the verbosity of `nih_plug_slint`'s diagnostic logging. This overrides the level set
+/// through the `NIH_PLUG_SLINT_LOG` environment variable, and it applies to all editor instances
+/// in the process.
+pub fn set_log_level(level: LogLevel) {
+    log_level_cell().store(level as u8, Ordering::Relaxed);
+}
+
+/// Get the current verbosity of `nih_plug_slint`'s diagnostic logging.
+pub fn log_level() -> LogLevel {
+    LogLevel::from_u8(log_level_cell().load(Ordering::Relaxed))
+}
+
+fn log_level_cell() -> &'static AtomicU8 {
+    LOG_LEVEL.get_or_init(|| {
+        let level = std::env::var(LOG_LEVEL_ENV_VAR)
+            .ok()
+            .and_then(|name| LogLevel::from_name(&name))
+            .unwrap_or(LogLevel::Warn);
+
+        AtomicU8::new(level as u8)
+    })
+}
+
+/// Write a message to the log file if `level` is enabled. The message is only formatted when it
+/// will actually be written. Use the `*_log!()` macros instead of calling this directly.
+pub(crate) fn log(level: LogLevel, message: impl FnOnce() -> String) {
+    if level == LogLevel::Off || level > log_level() {
+        return;
+    }
+
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(std::env::temp_dir().join(LOG_FILE_NAME))
+    {
+        let _ = writeln!(file, "[{:?}] {}", level, message());
+    }
+}
+
+macro_rules! error_log {
+    ($($args:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Error, || format!($($args)*))
+    };
+}
+
+macro_rules! warn_log {
+    ($($args:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Warn, || format!($($args)*))
+    };
+}
+
+macro_rules! info_log {
+    ($($args:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Info, || format!($($args)*))
+    };
+}
+
+macro_rules! debug_log {
+    ($($args:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Debug, || format!($($args)*))
+    };
+}
+
+macro_rules! trace_log {
+    ($($args:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Trace, || format!($($args)*))
+    };
+}
+
+pub(crate) use {debug_log, error_log, info_log, trace_log, warn_log};
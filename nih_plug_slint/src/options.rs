@@ -0,0 +1,471 @@
+//! Additional options for configuring a Slint editor.
+
+use crate::assets::SlintAssetDirectory;
+use crate::focus_ring::SlintFocusRing;
+use crate::logging::warn_log;
+use crate::splash::SlintSplash;
+use keyboard_types::{Key, Modifiers};
+use slint::platform::software_renderer::RepaintBufferType;
+use slint::LogicalPosition;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// The default for [`SlintEditorOptions::double_click_interval`].
+pub const DEFAULT_DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// The name of the environment variable that overrides the editor's scale factor. This is meant
+/// for reproducing and debugging HiDPI issues without rebuilding the plugin.
+const FORCE_SCALE_ENV_VAR: &str = "NIH_PLUG_SLINT_FORCE_SCALE";
+
+/// The name of the environment variable that overrides the repaint buffer type. This is an escape
+/// hatch for rendering artifacts in specific hosts that doesn't require rebuilding the plugin.
+/// Accepts `new`, `reused`, or `swapped`.
+const REPAINT_BUFFER_ENV_VAR: &str = "NIH_PLUG_SLINT_REPAINT_BUFFER";
+
+/// The name of the environment variable that makes the given number of presents fail after the
+/// editor opens, as if the window's softbuffer surface had been lost. This is meant for testing
+/// the recovery from lost surfaces without waiting for a display to go to sleep.
+const SIMULATE_PRESENT_FAILURES_ENV_VAR: &str = "NIH_PLUG_SLINT_SIMULATE_PRESENT_FAILURES";
+
+/// Options for a Slint editor created with [`create_slint_editor_with_options()`]. Use
+/// `..Default::default()` to only override the options you care about:
+///
+/// ```ignore
+/// create_slint_editor_with_options(
+///     self.params.editor_state.clone(),
+///     component_factory,
+///     None,
+///     SlintEditorOptions {
+///         render_scale: 2.0,
+///         ..Default::default()
+///     },
+/// )
+/// ```
+///
+/// [`create_slint_editor_with_options()`]: crate::create_slint_editor_with_options()
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlintEditorOptions {
+    /// The resolution Slint renders at relative to the window's physical resolution. Values above
+    /// 1.0 supersample the GUI and downscale the result to the window's size, which smooths out
+    /// edges at the cost of more rendering work. Values below 1.0 render at a lower resolution
+    /// and upscale the result, which reduces CPU usage for expensive GUIs on large HiDPI
+    /// windows. Mouse coordinates are not affected. Defaults to 1.0.
+    ///
+    /// Slint's software renderer always antialiases shapes and text and doesn't offer any quality
+    /// settings of its own, so this is the main way to trade rendering quality for speed.
+    pub render_scale: f32,
+    /// Whether Slint should only repaint the parts of the window that changed since the previous
+    /// frame. This is Slint's default and it's much cheaper for GUIs where only small parts change
+    /// at a time, like a few meters. Disabling this repaints the entire window for every frame
+    /// where anything changed, which can be useful for GUIs that change almost entirely every frame
+    /// anyway or to rule out partial rendering artifacts. Defaults to `true`.
+    pub partial_rendering: bool,
+    /// Overrides the repaint buffer type Slint's renderer assumes, which is otherwise derived
+    /// from [`partial_rendering`][Self::partial_rendering]. The default of
+    /// [`RepaintBufferType::ReusedBuffer`] may cause rendering artifacts with some hosts and
+    /// window systems, in which case [`RepaintBufferType::SwappedBuffers`] or
+    /// [`RepaintBufferType::NewBuffer`] can be tried. The `NIH_PLUG_SLINT_REPAINT_BUFFER`
+    /// environment variable takes precedence over this, so users can try the same without a
+    /// rebuild by setting it to `new`, `reused`, or `swapped`. Defaults to `None`.
+    pub repaint_buffer: Option<RepaintBufferType>,
+    /// Only render and present frames when something changed, instead of on every frame. Slint
+    /// still redraws when properties change, animations run, the window is resized, or the
+    /// pointer interacts with the GUI, so for static GUIs without continuously changing content
+    /// this brings idle CPU usage down to almost zero. The downside is that the window is not
+    /// repainted if the window system discards its contents without telling us, which can happen
+    /// on some Linux setups. Call [`SlintState::invalidate()`][crate::SlintState::invalidate()]
+    /// if stale contents show up. Defaults to `false`.
+    pub redraw_on_demand: bool,
+    /// Render the component only when the editor opens, the window is resized, a parameter
+    /// changes, the component is rebuilt, or
+    /// [`SlintState::invalidate()`][crate::SlintState::invalidate()] is called, and present the
+    /// cached frame otherwise. Slint's renderer doesn't run at all in
+    /// between, which makes this the cheapest option for fully static GUIs, like utility plugins
+    /// that only show labels.
+    ///
+    /// This only works for GUIs without animations, hover effects, or other changes Slint would
+    /// normally redraw for by itself, since none of those show up on screen. Changes made to the
+    /// component from outside of the parameter callback, like through
+    /// [`SlintState::update_component()`][crate::SlintState::update_component()], need to be
+    /// followed by a call to `invalidate()`. Overlays are still drawn every frame. Defaults to
+    /// `false`.
+    pub static_content: bool,
+    /// Whether pointer positions should be clamped to the window's bounds while a mouse button is
+    /// held down. Hover positions are always clamped. By default positions during a drag are
+    /// passed through as is, even when they're outside of the window, so sliders and knobs keep
+    /// tracking the cursor. Defaults to `false`.
+    pub clamp_pointer_during_drag: bool,
+    /// Smooth the pointer positions passed to Slint during a drag with this time constant, which
+    /// is how long the smoothed position takes to cover roughly 63% of the distance to the actual
+    /// pointer. Some hosts deliver mouse events in bursts, which makes knob and slider drags
+    /// jerky. With smoothing, the dragged control glides toward the pointer once per frame
+    /// instead, at the cost of trailing slightly behind it. A time constant of one or two frames,
+    /// like 15 to 30 milliseconds, is usually enough. Hovering and clicking are never smoothed,
+    /// and a drag always ends at the exact release position. This is off by default since precise
+    /// tools like envelope editors usually want the raw positions. Defaults to `None`.
+    pub pointer_smoothing: Option<Duration>,
+    /// The longest time between two clicks for them to count as a double click. This applies to
+    /// Slint's `TouchArea` `double-clicked` callback and click counting, which is the usual way to
+    /// reset a control to its default value by calling
+    /// [`SlintParamContext::set_normalized()`][crate::SlintParamContext::set_normalized()] with
+    /// the parameter's `default_normalized_value()`. Users with accessibility needs may need more
+    /// time between clicks, so consider making this configurable in the plugin's settings. Slint
+    /// only supports a single interval, so this applies to all controls in the editor. Defaults to
+    /// [`DEFAULT_DOUBLE_CLICK_INTERVAL`].
+    pub double_click_interval: Duration,
+    /// How the window's DPI scale factor is chosen. Defaults to [`ScalePolicy::Automatic`].
+    pub scale_policy: ScalePolicy,
+    /// How requests made through
+    /// [`SlintMouseControl::enable_unbounded_movement()`][crate::SlintMouseControl::enable_unbounded_movement()]
+    /// are carried out. Defaults to [`UnboundedMovement::Native`].
+    pub unbounded_movement: UnboundedMovement,
+    /// The area the virtual pointer position is confined to during unbounded movement. Defaults
+    /// to [`UnboundedRegion::Unlimited`].
+    pub unbounded_region: UnboundedRegion,
+    /// The title of the editor's window. This is usually not visible since the window is embedded
+    /// in the host's window, but some window managers and accessibility tools do show it.
+    pub window_title: String,
+    /// Whether a panic in the component factory should be caught. By default the panic is logged
+    /// and an error panel with its message is shown in place of the component, so a broken GUI
+    /// can't take down the host. That also makes the panic hard to debug, since all that's left
+    /// is a log line. Disabling this during development lets the panic propagate with a regular
+    /// backtrace instead, which aborts the host process when the editor is first opened. Panics
+    /// while rebuilding the component are still caught by the frame loop, and panics in event
+    /// handling and rendering are always caught. Defaults to `true`.
+    pub catch_factory_panics: bool,
+    /// A directory with fonts and images shipped with the plugin. The fonts in it are registered
+    /// every time before the component factory runs, so the component can refer to them by their
+    /// family names. See [`SlintAssetDirectory`] for details. Defaults to `None`.
+    pub assets: Option<SlintAssetDirectory>,
+    /// Forwards specific keyboard keys to Slint with custom text. Most plugins won't need this.
+    /// See [`SlintKeyMapping`] for details. Defaults to `None`.
+    pub key_mapping: Option<SlintKeyMapping>,
+    /// Lets Tab and Shift+Tab move the keyboard focus between custom-drawn parameter controls.
+    /// See [`SlintFocusRing`] for details. Defaults to `None`.
+    pub focus_ring: Option<SlintFocusRing>,
+    /// A background color and logo shown while the component factory runs. See [`SlintSplash`]
+    /// for details. Defaults to `None`.
+    pub splash: Option<SlintSplash>,
+}
+
+/// Decides which keyboard keys are sent to the Slint component, and as what text. Set it as
+/// [`SlintEditorOptions::key_mapping`].
+///
+/// By default keyboard events go to the host, since plugins would otherwise swallow the host's
+/// own shortcuts like the space bar for starting playback. Only the context menu key and the
+/// modifier keys are passed to Slint. A key mapping can forward additional keys, for instance to
+/// make Tab insert a tab character into a code editor-like `TextInput`. The closure is called for
+/// every key press and release with the key and the held modifiers. Returning `Some(text)` sends
+/// the event to Slint with that text instead of passing it on to the host, and returning `None`
+/// falls back to the default handling. Special keys like the arrow keys use the text of the
+/// matching [`slint::platform::Key`], which can be converted with `char::from()`.
+///
+/// Whether the plugin's window receives keyboard events at all depends on the host.
+///
+/// # Example
+///
+/// ```ignore
+/// SlintEditorOptions {
+///     key_mapping: Some(SlintKeyMapping::new(|key, _modifiers| match key {
+///         Key::Tab => Some(String::from("\t")),
+///         _ => None,
+///     })),
+///     ..Default::default()
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SlintKeyMapping(Arc<dyn Fn(&Key, Modifiers) -> Option<String> + Send + Sync>);
+
+impl SlintKeyMapping {
+    /// Create a key mapping from a closure. See [`SlintKeyMapping`].
+    pub fn new(f: impl Fn(&Key, Modifiers) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// The text `key` should be sent to Slint with, or `None` to fall back to the default
+    /// handling.
+    pub(crate) fn map(&self, key: &Key, modifiers: Modifiers) -> Option<String> {
+        (self.0)(key, modifiers)
+    }
+}
+
+impl std::fmt::Debug for SlintKeyMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SlintKeyMapping").finish_non_exhaustive()
+    }
+}
+
+/// Two key mappings are only equal if they are clones of each other, since closures can't be
+/// compared.
+impl PartialEq for SlintKeyMapping {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Determines how unbounded mouse movement is implemented. Cursor warping behaves differently
+/// across platforms, window managers, and remote desktop setups, so this allows falling back to
+/// simpler behavior where warping causes problems.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnboundedMovement {
+    /// Hide the cursor and let baseview report virtual positions, restoring the cursor's position
+    /// afterwards if requested.
+    #[default]
+    Native,
+    /// Like [`UnboundedMovement::Native`], but never warp the cursor back to where the drag
+    /// started. Useful where warping the cursor is unreliable or not allowed, like on some Linux
+    /// window managers and under remote desktop software.
+    NoRestore,
+    /// Don't use baseview's cursor hiding and warping at all. Instead the native cursor is hidden
+    /// through a regular cursor change and a cursor is drawn into the editor's frame at the
+    /// pointer's position until the drag ends. Drags are limited by the screen's edges like with
+    /// [`UnboundedMovement::Disabled`], but the cursor behaves the same everywhere. Useful on
+    /// hosts or platforms where the native implementation misbehaves.
+    SoftwareCursor,
+    /// Ignore unbounded movement requests entirely. The cursor stays visible and drags are
+    /// limited by the screen's edges.
+    Disabled,
+}
+
+/// Confines the pointer position reported to the component during unbounded movement.
+///
+/// During unbounded movement baseview accumulates the mouse's movement into a virtual position
+/// that starts where the drag started, independently of where the screens are. That position is
+/// what the component sees, so on multi-monitor setups a long drag can report positions far
+/// beyond the editor's screen, and on some systems the physical cursor briefly crosses onto
+/// another screen between warps, which shows up as erratic jumps. Confining the virtual position
+/// to a region around the editor gives drags hard edges instead. Movement is accumulated relative
+/// to the confined position, so reversing direction at an edge takes effect immediately rather
+/// than after the overshoot has been undone.
+///
+/// Baseview doesn't expose which monitor the window is on or where that monitor's edges are, so
+/// the regions are expressed relative to the editor's window and the drag's starting position.
+/// The editor's window always lies on the monitor the host shows it on, so
+/// [`UnboundedRegion::Window`] with a margin smaller than the screen keeps drags on that monitor.
+/// This has no effect on [`UnboundedMovement::SoftwareCursor`] and
+/// [`UnboundedMovement::Disabled`], where the real cursor is already limited by the screens.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum UnboundedRegion {
+    /// Don't confine the virtual position at all.
+    #[default]
+    Unlimited,
+    /// Confine the virtual position to the editor's window, grown by `margin` logical pixels on
+    /// every side.
+    Window { margin: f32 },
+    /// Confine the virtual position to a `width * height` logical pixel rectangle centered on
+    /// where the drag started.
+    AroundOrigin { width: f32, height: f32 },
+}
+
+impl UnboundedRegion {
+    /// Confine `position` to this region. `origin` is where the drag started and `window_size`
+    /// is the window's logical size, both in the window's logical coordinates.
+    pub(crate) fn confine(
+        &self,
+        position: LogicalPosition,
+        origin: LogicalPosition,
+        window_size: (f32, f32),
+    ) -> LogicalPosition {
+        let (min_x, min_y, max_x, max_y) = match *self {
+            UnboundedRegion::Unlimited => return position,
+            UnboundedRegion::Window { margin } => {
+                let margin = margin.max(0.0);
+                (-margin, -margin, window_size.0 + margin, window_size.1 + margin)
+            }
+            UnboundedRegion::AroundOrigin { width, height } => {
+                let (half_width, half_height) = (width.max(0.0) / 2.0, height.max(0.0) / 2.0);
+                (
+                    origin.x - half_width,
+                    origin.y - half_height,
+                    origin.x + half_width,
+                    origin.y + half_height,
+                )
+            }
+        };
+
+        LogicalPosition::new(
+            position.x.clamp(min_x, max_x.max(min_x)),
+            position.y.clamp(min_y, max_y.max(min_y)),
+        )
+    }
+}
+
+/// Determines the DPI scale factor used for the editor's window.
+///
+/// For debugging scaling issues, the `NIH_PLUG_SLINT_FORCE_SCALE` environment variable can be set
+/// to a scale factor (e.g. `NIH_PLUG_SLINT_FORCE_SCALE=1.5`) to override the policy as if
+/// [`ScalePolicy::Fixed`] was used. This is read every time the editor opens and it has no effect
+/// when it's not set. It's not meant to be used in place of this option.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ScalePolicy {
+    /// Use the scale factor set by the host if it sets one. Otherwise use the system's scale
+    /// factor on macOS and a scale factor of 1.0 on Windows and Linux, since hosts that don't set
+    /// a scale factor there size the editor's window as if it wasn't scaled.
+    #[default]
+    Automatic,
+    /// Always use the system's scale factor, ignoring any scale factor set by the host. Useful
+    /// for hosts that report incorrect scale factors.
+    System,
+    /// Always use this fixed scale factor, ignoring both the host and the system. This makes the
+    /// editor look the same across hosts.
+    ///
+    /// On macOS, passing 1.0 here can cause the GUI to be scaled while the mouse events are not,
+    /// so on macOS [`ScalePolicy::System`] should be preferred.
+    Fixed(f64),
+}
+
+impl Default for SlintEditorOptions {
+    fn default() -> Self {
+        Self {
+            render_scale: 1.0,
+            partial_rendering: true,
+            repaint_buffer: None,
+            redraw_on_demand: false,
+            static_content: false,
+            clamp_pointer_during_drag: false,
+            pointer_smoothing: None,
+            double_click_interval: DEFAULT_DOUBLE_CLICK_INTERVAL,
+            scale_policy: ScalePolicy::default(),
+            unbounded_movement: UnboundedMovement::default(),
+            unbounded_region: UnboundedRegion::default(),
+            window_title: String::from("Slint Plugin Window"),
+            catch_factory_panics: true,
+            assets: None,
+            key_mapping: None,
+            focus_ring: None,
+            splash: None,
+        }
+    }
+}
+
+impl SlintEditorOptions {
+    /// The render scale with nonsensical values replaced by 1.0.
+    pub(crate) fn effective_render_scale(&self) -> f32 {
+        if self.render_scale.is_finite() && self.render_scale > 0.0 {
+            self.render_scale
+        } else {
+            1.0
+        }
+    }
+
+    /// The repaint buffer type forced through the `NIH_PLUG_SLINT_REPAINT_BUFFER` environment
+    /// variable, or [`repaint_buffer`][Self::repaint_buffer], or the type matching
+    /// [`partial_rendering`][Self::partial_rendering], in that order.
+    pub(crate) fn repaint_buffer_type(&self) -> RepaintBufferType {
+        if let Some(repaint_buffer) = forced_repaint_buffer_type().or(self.repaint_buffer) {
+            return repaint_buffer;
+        }
+
+        if self.partial_rendering {
+            RepaintBufferType::ReusedBuffer
+        } else {
+            RepaintBufferType::NewBuffer
+        }
+    }
+
+    /// The scale policy, or a fixed scale factor if one is forced through the
+    /// `NIH_PLUG_SLINT_FORCE_SCALE` environment variable.
+    pub(crate) fn effective_scale_policy(&self) -> ScalePolicy {
+        let Ok(value) = std::env::var(FORCE_SCALE_ENV_VAR) else {
+            return self.scale_policy;
+        };
+
+        match value.trim().parse::<f64>() {
+            Ok(factor) if factor.is_finite() && factor > 0.0 => {
+                warn_log!("Forcing a scale factor of {} through {}", factor, FORCE_SCALE_ENV_VAR);
+                ScalePolicy::Fixed(factor)
+            }
+            _ => {
+                warn_log!("Ignoring invalid {} value '{}'", FORCE_SCALE_ENV_VAR, value);
+                self.scale_policy
+            }
+        }
+    }
+}
+
+/// The repaint buffer type forced through the `NIH_PLUG_SLINT_REPAINT_BUFFER` environment
+/// variable, if any. The variable is only read once per process.
+pub(crate) fn forced_repaint_buffer_type() -> Option<RepaintBufferType> {
+    static FORCED: OnceLock<Option<RepaintBufferType>> = OnceLock::new();
+
+    *FORCED.get_or_init(|| {
+        let value = std::env::var(REPAINT_BUFFER_ENV_VAR).ok()?;
+        let repaint_buffer = parse_repaint_buffer_type(&value);
+        match repaint_buffer {
+            Some(repaint_buffer) => warn_log!(
+                "Forcing the {:?} repaint buffer type through {}",
+                repaint_buffer,
+                REPAINT_BUFFER_ENV_VAR
+            ),
+            None => warn_log!("Ignoring invalid {} value '{}'", REPAINT_BUFFER_ENV_VAR, value),
+        }
+
+        repaint_buffer
+    })
+}
+
+/// The number of presents that should fail on purpose, set through the
+/// `NIH_PLUG_SLINT_SIMULATE_PRESENT_FAILURES` environment variable. This is read every time an
+/// editor is opened.
+pub(crate) fn simulated_present_failures() -> u32 {
+    let Ok(value) = std::env::var(SIMULATE_PRESENT_FAILURES_ENV_VAR) else {
+        return 0;
+    };
+
+    match value.trim().parse() {
+        Ok(count) => {
+            warn_log!("Simulating {} failed presents", count);
+            count
+        }
+        Err(_) => {
+            warn_log!("Ignoring invalid {} value '{}'", SIMULATE_PRESENT_FAILURES_ENV_VAR, value);
+            0
+        }
+    }
+}
+
+/// Parse a repaint buffer type from its name, ignoring case and surrounding whitespace.
+fn parse_repaint_buffer_type(value: &str) -> Option<RepaintBufferType> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "new" => Some(RepaintBufferType::NewBuffer),
+        "reused" => Some(RepaintBufferType::ReusedBuffer),
+        "swapped" => Some(RepaintBufferType::SwappedBuffers),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repaint_buffer_type_names() {
+        assert_eq!(
+            parse_repaint_buffer_type(" Swapped\n"),
+            Some(RepaintBufferType::SwappedBuffers)
+        );
+        assert_eq!(parse_repaint_buffer_type("new"), Some(RepaintBufferType::NewBuffer));
+        assert_eq!(parse_repaint_buffer_type("double"), None);
+    }
+
+    #[test]
+    fn unbounded_region_confines_position() {
+        let origin = LogicalPosition::new(50.0, 50.0);
+        let far = LogicalPosition::new(-5000.0, 3000.0);
+
+        assert_eq!(UnboundedRegion::Unlimited.confine(far, origin, (100.0, 100.0)), far);
+        assert_eq!(
+            UnboundedRegion::Window { margin: 20.0 }.confine(far, origin, (100.0, 100.0)),
+            LogicalPosition::new(-20.0, 120.0)
+        );
+        assert_eq!(
+            UnboundedRegion::AroundOrigin {
+                width: 400.0,
+                height: 200.0
+            }
+            .confine(far, origin, (100.0, 100.0)),
+            LogicalPosition::new(-150.0, 150.0)
+        );
+    }
+}
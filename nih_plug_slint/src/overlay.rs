@@ -0,0 +1,82 @@
+//! Overlays drawn directly into the rendered frame.
+
+/// A rendered frame passed to an overlay set with
+/// [`SlintState::set_overlay()`][crate::SlintState::set_overlay()].
+///
+/// The pixels are stored row by row starting at the window's top left corner, with `width` pixels
+/// per row and no padding. Their coordinates are in render pixels: a position in Slint's logical
+/// pixels can be converted by multiplying it by `scale_factor` and rounding. `scale_factor`
+/// includes both the display's scale factor and
+/// [`SlintEditorOptions::render_scale`][crate::SlintEditorOptions::render_scale].
+pub struct SlintOverlayFrame<'a> {
+    /// The frame's pixels. Changes made here are shown on screen but are not seen by Slint.
+    pub pixels: &'a mut [slint::Rgb8Pixel],
+    /// The frame's width in render pixels.
+    pub width: u32,
+    /// The frame's height in render pixels.
+    pub height: u32,
+    /// The number of render pixels per logical pixel.
+    pub scale_factor: f32,
+}
+
+impl SlintOverlayFrame<'_> {
+    /// Get a mutable reference to the pixel at `(x, y)`, or `None` if that's outside of the frame.
+    pub fn pixel_mut(&mut self, x: u32, y: u32) -> Option<&mut slint::Rgb8Pixel> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.pixels.get_mut(y as usize * self.width as usize + x as usize)
+    }
+}
+
+/// A classic arrow cursor. `X` is the outline, `.` is the fill, and spaces are transparent.
+const SOFTWARE_CURSOR: [&str; 17] = [
+    "X",
+    "XX",
+    "X.X",
+    "X..X",
+    "X...X",
+    "X....X",
+    "X.....X",
+    "X......X",
+    "X.......X",
+    "X........X",
+    "X.....XXXXX",
+    "X..X..X",
+    "X.X X..X",
+    "XX  X..X",
+    "X    X..X",
+    "     X..X",
+    "      XX",
+];
+
+/// Draw an arrow cursor with its tip at the logical position `(x, y)`. Used for
+/// [`UnboundedMovement::SoftwareCursor`][crate::UnboundedMovement::SoftwareCursor].
+pub(crate) fn draw_software_cursor(frame: &mut SlintOverlayFrame, x: f32, y: f32) {
+    let scale = frame.scale_factor.round().max(1.0) as u32;
+    let tip_x = (x * frame.scale_factor).round() as i64;
+    let tip_y = (y * frame.scale_factor).round() as i64;
+
+    for (row, line) in SOFTWARE_CURSOR.iter().enumerate() {
+        for (column, symbol) in line.chars().enumerate() {
+            let color = match symbol {
+                'X' => slint::Rgb8Pixel::new(0, 0, 0),
+                '.' => slint::Rgb8Pixel::new(255, 255, 255),
+                _ => continue,
+            };
+
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    let px = tip_x + (column as u32 * scale + dx) as i64;
+                    let py = tip_y + (row as u32 * scale + dy) as i64;
+                    if let (Ok(px), Ok(py)) = (u32::try_from(px), u32::try_from(py)) {
+                        if let Some(pixel) = frame.pixel_mut(px, py) {
+                            *pixel = color;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
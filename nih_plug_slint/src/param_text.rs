@@ -0,0 +1,70 @@
+//! A plain text format for copying and pasting parameter values.
+//!
+//! The text starts with a [`HEADER`] line, followed by one `id=value` line per parameter with the
+//! parameter's normalized value. See
+//! [`SlintParamContext::params_to_string()`][crate::SlintParamContext::params_to_string()].
+
+/// The first line of every blob. This identifies the format, and the version number allows it to
+/// change later without misinterpreting older blobs.
+const HEADER: &str = "nih-plug-slint params v1";
+
+/// Format `(id, normalized value)` pairs as a blob.
+pub(crate) fn format_param_values<'a>(values: impl IntoIterator<Item = (&'a str, f32)>) -> String {
+    let mut text = String::from(HEADER);
+    for (id, normalized) in values {
+        text.push('\n');
+        text.push_str(id);
+        text.push('=');
+        text.push_str(&normalized.to_string());
+    }
+
+    text
+}
+
+/// Parse a blob created by [`format_param_values()`]. Surrounding whitespace and empty lines are
+/// ignored. Returns `None` if the header is missing or any line is malformed, so a partially
+/// valid blob is never applied.
+pub(crate) fn parse_param_values(text: &str) -> Option<Vec<(String, f32)>> {
+    let mut lines = text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty());
+    if lines.next()? != HEADER {
+        return None;
+    }
+
+    lines
+        .map(|line| {
+            // The value never contains an equals sign, but the ID might
+            let (id, value) = line.rsplit_once('=')?;
+            let normalized: f32 = value.parse().ok()?;
+            if id.is_empty() || !(0.0..=1.0).contains(&normalized) {
+                return None;
+            }
+
+            Some((id.to_owned(), normalized))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blobs_round_trip_and_malformed_blobs_are_rejected() {
+        let text = format_param_values([("gain", 0.25), ("a=b", 1.0)]);
+        assert_eq!(
+            parse_param_values(&format!("  {text}\n\n")),
+            Some(vec![("gain".to_owned(), 0.25), ("a=b".to_owned(), 1.0)])
+        );
+        assert_eq!(parse_param_values(HEADER), Some(Vec::new()));
+
+        assert_eq!(parse_param_values(""), None);
+        assert_eq!(parse_param_values("gain=0.25"), None);
+        assert_eq!(parse_param_values(&format!("{HEADER}\ngain")), None);
+        assert_eq!(parse_param_values(&format!("{HEADER}\ngain=loud")), None);
+        assert_eq!(parse_param_values(&format!("{HEADER}\ngain=1.5")), None);
+        assert_eq!(parse_param_values(&format!("{HEADER}\n=0.5")), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Looking up the scale factor of the host's window before the editor's own window is opened.
+
+use nih_plug::prelude::ParentWindowHandle;
+
+/// The system scale factor that applies to the host's `parent` window, the same way baseview
+/// determines it for [`baseview::WindowScalePolicy::SystemScaleFactor`]. This lets the editor set
+/// up its first frame at the right size instead of waiting for baseview's first `Resized` event.
+/// Returns `None` if the scale factor can't be determined.
+#[cfg(target_os = "macos")]
+pub(crate) fn parent_scale_factor(parent: ParentWindowHandle) -> Option<f32> {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    let ParentWindowHandle::AppKitNsView(ns_view) = parent else {
+        return None;
+    };
+
+    unsafe {
+        // The view may not be in a window yet, in which case the main screen is the best guess
+        let ns_view = ns_view as *mut Object;
+        let ns_window: *mut Object = if ns_view.is_null() {
+            std::ptr::null_mut()
+        } else {
+            msg_send![ns_view, window]
+        };
+        let scale_factor: f64 = if ns_window.is_null() {
+            let screen: *mut Object = msg_send![class!(NSScreen), mainScreen];
+            if screen.is_null() {
+                return None;
+            }
+            msg_send![screen, backingScaleFactor]
+        } else {
+            msg_send![ns_window, backingScaleFactor]
+        };
+
+        (scale_factor > 0.0).then_some(scale_factor as f32)
+    }
+}
+
+/// The system scale factor that applies to the host's `parent` window, the same way baseview
+/// determines it for [`baseview::WindowScalePolicy::SystemScaleFactor`]. This lets the editor set
+/// up its first frame at the right size instead of waiting for baseview's first `Resized` event.
+/// Returns `None` if the scale factor can't be determined.
+#[cfg(target_os = "windows")]
+pub(crate) fn parent_scale_factor(parent: ParentWindowHandle) -> Option<f32> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::HiDpi::GetDpiForWindow;
+
+    let ParentWindowHandle::Win32Hwnd(hwnd) = parent else {
+        return None;
+    };
+
+    // This returns 0 for invalid windows
+    let dpi = unsafe { GetDpiForWindow(HWND(hwnd as isize)) };
+    (dpi > 0).then_some(dpi as f32 / 96.0)
+}
+
+/// The system scale factor that applies to the host's `parent` window, the same way baseview
+/// determines it for [`baseview::WindowScalePolicy::SystemScaleFactor`]. This lets the editor set
+/// up its first frame at the right size instead of waiting for baseview's first `Resized` event.
+/// Returns `None` if the scale factor can't be determined.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn parent_scale_factor(parent: ParentWindowHandle) -> Option<f32> {
+    use crate::logging::debug_log;
+    use x11rb::connection::Connection;
+
+    if !matches!(parent, ParentWindowHandle::X11Window(_)) {
+        return None;
+    }
+
+    let (connection, screen_num) = match x11rb::connect(None) {
+        Ok(connection) => connection,
+        Err(e) => {
+            debug_log!("Could not look up the scale factor through X11: {}", e);
+            return None;
+        }
+    };
+
+    // Like baseview, `Xft.dpi` takes precedence and the screen's physical DPI is the fallback
+    let xft_dpi = x11rb::resource_manager::new_from_default(&connection)
+        .ok()
+        .and_then(|database| database.get_value::<f32>("Xft.dpi", "").ok().flatten());
+    let dpi = match xft_dpi {
+        Some(dpi) => dpi,
+        None => {
+            let screen = connection.setup().roots.get(screen_num)?;
+            if screen.height_in_millimeters == 0 {
+                return None;
+            }
+            screen.height_in_pixels as f32 * 25.4 / screen.height_in_millimeters as f32
+        }
+    };
+
+    (dpi.is_finite() && dpi > 0.0).then_some(dpi / 96.0)
+}
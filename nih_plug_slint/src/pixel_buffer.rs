@@ -0,0 +1,66 @@
+//! The intermediate buffer Slint renders into before the result is copied to softbuffer.
+
+use slint::Rgb8Pixel;
+
+/// A pixel buffer that only ever grows. When the window is resized, only the used length changes
+/// and the pixels past it are kept. A `Vec` resized to the new size also keeps its allocation,
+/// but it fills every pixel it grows by again, which this buffer skips when it grows back to a
+/// size it had before while a window edge is dragged back and forth.
+pub struct PixelBuffer {
+    /// The backing storage. This is at least `len` pixels long, and it's never shrunk.
+    pixels: Vec<Rgb8Pixel>,
+    /// The number of pixels in use, i.e. `width * height`.
+    len: usize,
+}
+
+impl PixelBuffer {
+    /// Create a buffer for a `width * height` image.
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = width as usize * height as usize;
+
+        Self {
+            pixels: vec![Rgb8Pixel::default(); len],
+            len,
+        }
+    }
+
+    /// Change the buffer's size. This only reallocates if the new size is larger than any
+    /// previous size.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.len = width as usize * height as usize;
+        if self.len > self.pixels.len() {
+            self.pixels.resize(self.len, Rgb8Pixel::default());
+        }
+    }
+
+    /// The pixels for the current size.
+    pub fn pixels(&self) -> &[Rgb8Pixel] {
+        &self.pixels[..self.len]
+    }
+
+    /// The pixels for the current size.
+    pub fn pixels_mut(&mut self) -> &mut [Rgb8Pixel] {
+        &mut self.pixels[..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_reuses_allocation() {
+        let mut buffer = PixelBuffer::new(800, 600);
+        let allocation = buffer.pixels.as_ptr();
+
+        // Simulates dragging a window edge back and forth below the initial size
+        for size in (100..800).step_by(7).chain((100..800).step_by(13).rev()) {
+            buffer.resize(size, size * 3 / 4);
+            assert_eq!(buffer.pixels().len(), (size * (size * 3 / 4)) as usize);
+            assert_eq!(buffer.pixels.as_ptr(), allocation);
+        }
+
+        buffer.resize(1000, 1000);
+        assert_eq!(buffer.pixels().len(), 1000 * 1000);
+    }
+}
@@ -1,32 +1,36 @@
 //! Custom Slint Platform implementation for NIH-plug.
 //!
-//! Since `slint::platform::set_platform()` can only be called once per process,
-//! we use a global platform that can handle multiple plugin instances.
+//! Slint's platform and all of its state are stored per thread, and
+//! `slint::platform::set_platform()` can only be called once per thread. The platform is set up on
+//! the thread running the editor's window the first time a component is created there, and it's
+//! shared by all plugin instances whose windows run on that thread. Depending on the platform,
+//! baseview runs windows either on the host's GUI thread or on a thread of their own.
 
-use slint::platform::software_renderer::MinimalSoftwareWindow;
+use crate::error::SlintEditorError;
+use crate::logging::{debug_log, error_log, warn_log};
+use crate::options::{forced_repaint_buffer_type, DEFAULT_DOUBLE_CLICK_INTERVAL};
+use slint::platform::software_renderer::{MinimalSoftwareWindow, RepaintBufferType};
 use slint::platform::{Platform, PlatformError, WindowAdapter};
-use std::cell::RefCell;
-use std::io::Write;
+use std::cell::{Cell, OnceCell, RefCell};
 use std::rc::Rc;
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/nih_plug_slint_debug.log")
-    {
-        let _ = writeln!(file, "{}", msg);
-    }
-}
-
+/// The time the first platform was set up at. Slint's animations only need a monotonic clock, so
+/// this is shared between threads.
 static PLATFORM_START_TIME: OnceLock<Instant> = OnceLock::new();
 
 thread_local! {
+    /// Whether the platform was set up on this thread, or the reason it couldn't be set up.
+    static PLATFORM_RESULT: OnceCell<Result<(), SlintEditorError>> = const { OnceCell::new() };
+
     /// Thread-local storage for the window to use when creating a component.
     /// This allows us to inject our own MinimalSoftwareWindow into component creation.
     static PENDING_WINDOW: RefCell<Option<Rc<MinimalSoftwareWindow>>> = const { RefCell::new(None) };
+
+    /// The double click interval of the editor that's currently handling an event. The platform
+    /// is shared by all editors on a thread, so this is set before every event.
+    static CLICK_INTERVAL: Cell<Duration> = const { Cell::new(DEFAULT_DOUBLE_CLICK_INTERVAL) };
 }
 
 /// Sets the window that should be used for the next component creation on this thread.
@@ -37,31 +41,47 @@ pub fn set_pending_window(window: Rc<MinimalSoftwareWindow>) {
     });
 }
 
-/// Ensures the Slint platform is initialized. This function is idempotent and safe
-/// to call multiple times - it will only initialize the platform once.
-pub fn ensure_slint_platform() {
-    debug_log("ensure_slint_platform() called");
-
-    // Initialize the start time - this will only run once
-    PLATFORM_START_TIME.get_or_init(|| {
-        debug_log("First-time platform initialization...");
-        let start_time = Instant::now();
-
-        let platform = NihPlugSlintPlatform;
-        match slint::platform::set_platform(Box::new(platform)) {
-            Ok(()) => {
-                debug_log("Slint platform set successfully");
-            }
-            Err(e) => {
-                debug_log(&format!("FAILED to set Slint platform: {:?}", e));
-                panic!("Failed to set Slint platform - another platform may already be set: {:?}", e);
-            }
-        }
+/// Takes the window set with [`set_pending_window()`] if no component has used it yet.
+pub fn take_pending_window() -> Option<Rc<MinimalSoftwareWindow>> {
+    PENDING_WINDOW.with(|cell| cell.borrow_mut().take())
+}
 
-        start_time
+/// Sets the double click interval Slint uses on this thread until it's set again. See
+/// [`SlintEditorOptions::double_click_interval`][crate::SlintEditorOptions::double_click_interval].
+pub fn set_click_interval(interval: Duration) {
+    CLICK_INTERVAL.with(|cell| cell.set(interval));
+}
+
+/// Ensures the Slint platform is initialized on the current thread. This function is idempotent
+/// and safe to call multiple times - it will only initialize the platform once per thread. If that
+/// failed, then the same error is returned on every call from that thread.
+pub fn ensure_slint_platform() -> Result<(), SlintEditorError> {
+    debug_log!("ensure_slint_platform() called");
+
+    // This will only run once per thread
+    let result = PLATFORM_RESULT.with(|result| {
+        result
+            .get_or_init(|| {
+                debug_log!("First-time platform initialization on this thread...");
+                PLATFORM_START_TIME.get_or_init(Instant::now);
+
+                let platform = NihPlugSlintPlatform;
+                match slint::platform::set_platform(Box::new(platform)) {
+                    Ok(()) => {
+                        debug_log!("Slint platform set successfully");
+                        Ok(())
+                    }
+                    Err(e) => {
+                        error_log!("FAILED to set Slint platform: {:?}", e);
+                        Err(SlintEditorError::PlatformAlreadySet(e.to_string()))
+                    }
+                }
+            })
+            .clone()
     });
 
-    debug_log("ensure_slint_platform() completed");
+    debug_log!("ensure_slint_platform() completed");
+    result
 }
 
 /// Custom Slint platform for NIH-plug integration.
@@ -80,13 +100,13 @@ impl Platform for NihPlugSlintPlatform {
 
         if let Some(window) = pending {
             // Use the pre-created window
-            debug_log("Using pending window!");
+            debug_log!("Using pending window!");
             Ok(window)
         } else {
             // Create a new MinimalSoftwareWindow as fallback
-            debug_log("Creating fallback window (NOT using our window!)");
+            warn_log!("Creating fallback window (NOT using our window!)");
             Ok(MinimalSoftwareWindow::new(
-                slint::platform::software_renderer::RepaintBufferType::ReusedBuffer,
+                forced_repaint_buffer_type().unwrap_or(RepaintBufferType::ReusedBuffer),
             ))
         }
     }
@@ -98,6 +118,10 @@ impl Platform for NihPlugSlintPlatform {
             .unwrap_or_default()
     }
 
+    fn click_interval(&self) -> Duration {
+        CLICK_INTERVAL.with(|cell| cell.get())
+    }
+
     // We don't implement run_event_loop() because baseview drives the event loop.
     // The default implementation returns an error, which is correct for our use case.
 }
@@ -0,0 +1,102 @@
+//! Helpers for positioning popups and combobox dropdowns.
+//!
+//! Our Slint platform renders everything into a single `MinimalSoftwareWindow`, so Slint's
+//! `PopupWindow`s and `ComboBox` dropdowns are drawn inside of the editor window instead of in
+//! separate top-level windows. Anything that extends past the editor's bounds is simply clipped.
+//! Slint already keeps its built-in `ComboBox` dropdowns inside of the window, but custom
+//! `PopupWindow`s are shown at whatever `x` and `y` they're given. Use [`popup_position()`] to
+//! compute a position that keeps such a popup fully visible:
+//!
+//! ```ignore
+//! // In the .slint file, `menu` is a `PopupWindow` with `x: root.menu-x; y: root.menu-y;`, and
+//! // the button calls `root.open-menu(self.absolute-position.x, self.absolute-position.y,
+//! // self.height)` before `menu.show()`.
+//!
+//! // In the component factory:
+//! ui.on_open_menu({
+//!     let ui = ui.as_weak();
+//!     move |x, y, anchor_height| {
+//!         let ui = ui.unwrap();
+//!         let window = ui.window();
+//!         let position = nih_plug_slint::popup_position(
+//!             slint::LogicalPosition::new(x, y),
+//!             anchor_height,
+//!             slint::LogicalSize::new(160.0, 200.0),
+//!             window.size().to_logical(window.scale_factor()),
+//!         );
+//!         ui.set_menu_x(position.x);
+//!         ui.set_menu_y(position.y);
+//!     }
+//! });
+//! ```
+
+use slint::{LogicalPosition, LogicalSize};
+
+/// Compute the top-left position for a popup of `popup_size` anchored to a control whose top-left
+/// corner is at `anchor` and that is `anchor_height` logical pixels tall, so that the popup stays
+/// within a window of `window_size`. The popup is placed directly below the control if it fits,
+/// otherwise it's flipped above the control. If it fits in neither place, or if it's wider than
+/// the remaining space, then it's shifted so it stays inside of the window as much as possible.
+pub fn popup_position(
+    anchor: LogicalPosition,
+    anchor_height: f32,
+    popup_size: LogicalSize,
+    window_size: LogicalSize,
+) -> LogicalPosition {
+    let below = anchor.y + anchor_height;
+    let above = anchor.y - popup_size.height;
+    let y = if below + popup_size.height <= window_size.height {
+        below
+    } else if above >= 0.0 {
+        above
+    } else {
+        clamp_to_window(below, popup_size.height, window_size.height)
+    };
+    let x = clamp_to_window(anchor.x, popup_size.width, window_size.width);
+
+    LogicalPosition::new(x, y)
+}
+
+/// Clamp the start of a span of `length` pixels so it ends before `window_length`. If the span is
+/// longer than the window, then it's aligned to the start of the window.
+fn clamp_to_window(start: f32, length: f32, window_length: f32) -> f32 {
+    start.min(window_length - length).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn popup_placement() {
+        let window_size = LogicalSize::new(400.0, 300.0);
+        let popup_size = LogicalSize::new(100.0, 80.0);
+
+        // Fits below the control
+        let position = popup_position(
+            LogicalPosition::new(10.0, 10.0),
+            20.0,
+            popup_size,
+            window_size,
+        );
+        assert_eq!((position.x, position.y), (10.0, 30.0));
+
+        // Flipped above the control, and shifted left to stay inside the window
+        let position = popup_position(
+            LogicalPosition::new(350.0, 250.0),
+            20.0,
+            popup_size,
+            window_size,
+        );
+        assert_eq!((position.x, position.y), (300.0, 170.0));
+
+        // Taller than the window, so it's aligned to the top
+        let position = popup_position(
+            LogicalPosition::new(10.0, 100.0),
+            20.0,
+            LogicalSize::new(100.0, 500.0),
+            window_size,
+        );
+        assert_eq!((position.x, position.y), (10.0, 0.0));
+    }
+}
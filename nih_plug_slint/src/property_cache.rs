@@ -0,0 +1,125 @@
+//! Skipping redundant property writes in the parameter changed callback.
+
+use parking_lot::Mutex;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Remembers the last value written to each Slint property so the parameter changed callback
+/// only calls setters for values that actually changed. The callback passed to
+/// [`create_slint_editor_with_param_callback()`][crate::create_slint_editor_with_param_callback()]
+/// runs whenever any parameter changes, so during automation a callback that sets every property
+/// spends most of its time rewriting values that are already there. Every setter call goes through
+/// the component's generated code and replaces any binding on the property, and values derived
+/// from the parameters, like formatted strings, have to be converted to Slint types first. Going
+/// through this cache keeps that work proportional to the number of parameters that changed.
+///
+/// Properties are identified by a key, usually the property's name. Values of any type that can
+/// be compared can be cached, and a key can only hold one type at a time. Writing a value of a
+/// different type to an existing key always calls the setter.
+///
+/// The cache has no way to know when a new component is created, for instance when the editor
+/// is reopened or the component is rebuilt, so call [`clear()`][Self::clear()] from the component
+/// factory. Otherwise the new component keeps its default values for any parameter that hasn't
+/// changed since the old component was last updated. The cache can be cloned, and all clones
+/// share the same values.
+///
+/// # Example
+///
+/// ```ignore
+/// let property_cache = SlintPropertyCache::new();
+///
+/// create_slint_editor_with_param_callback(
+///     self.params.editor_state.clone(),
+///     {
+///         let property_cache = property_cache.clone();
+///         move |gui_context, _mouse_control| {
+///             property_cache.clear();
+///             PluginUI::new().unwrap()
+///         }
+///     },
+///     Some(Arc::new(move |ui: &PluginUI| {
+///         property_cache.set("gain", params.gain.unmodulated_normalized_value(), |value| {
+///             ui.set_gain(value)
+///         });
+///         property_cache.set("gain-text", params.gain.to_string(), |text| {
+///             ui.set_gain_text(text.into())
+///         });
+///     })),
+/// )
+/// ```
+#[derive(Clone, Default)]
+pub struct SlintPropertyCache {
+    values: Arc<Mutex<HashMap<&'static str, Box<dyn Any + Send>>>>,
+}
+
+impl std::fmt::Debug for SlintPropertyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlintPropertyCache")
+            .field("len", &self.values.lock().len())
+            .finish()
+    }
+}
+
+impl SlintPropertyCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `setter` with `value` unless the last value written for `key` is equal to it.
+    /// Returns whether the setter was called.
+    pub fn set<T: PartialEq + Clone + Send + 'static>(
+        &self,
+        key: &'static str,
+        value: T,
+        setter: impl FnOnce(T),
+    ) -> bool {
+        let mut values = self.values.lock();
+        if values
+            .get(key)
+            .and_then(|last| last.downcast_ref::<T>())
+            .is_some_and(|last| *last == value)
+        {
+            return false;
+        }
+
+        values.insert(key, Box::new(value.clone()));
+        // The setter may trigger change callbacks that use this cache as well
+        drop(values);
+        setter(value);
+
+        true
+    }
+
+    /// Forget all cached values, so the next [`set()`][Self::set()] for every key calls its
+    /// setter.
+    pub fn clear(&self) {
+        self.values.lock().clear();
+    }
+
+    /// Forget the cached value for `key`, for instance after the property was changed from
+    /// somewhere else.
+    pub fn forget(&self, key: &'static str) {
+        self.values.lock().remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_values_are_skipped() {
+        let cache = SlintPropertyCache::new();
+        let mut written = Vec::new();
+
+        assert!(cache.set("gain", 0.5f32, |value| written.push(value)));
+        assert!(!cache.set("gain", 0.5f32, |value| written.push(value)));
+        assert!(cache.set("gain", 0.75f32, |value| written.push(value)));
+
+        cache.clear();
+        assert!(cache.set("gain", 0.75f32, |value| written.push(value)));
+        assert_eq!(written, [0.5, 0.75, 0.75]);
+    }
+}
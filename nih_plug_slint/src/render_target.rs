@@ -0,0 +1,29 @@
+//! Rendering into a frame buffer owned by the host or another integration.
+
+/// A destination for the editor's frames in place of its own window, for embedding the GUI in a
+/// custom compositor or another non-standard context. Pass one to
+/// [`create_slint_editor_with_render_target()`][crate::create_slint_editor_with_render_target()].
+///
+/// The editor still opens its window as usual so it keeps receiving mouse and keyboard input
+/// from baseview, but it doesn't create a softbuffer surface for it and never draws to it.
+/// Instead, every finished frame is passed to [`present()`][Self::present()]. Overlays, the
+/// software cursor, and [snapshots][crate::SlintState::request_snapshot()] work the same as with
+/// the default window surface.
+///
+/// # Threading
+///
+/// The target is shared with the editor, which may be created on a different thread than the one
+/// its window runs on, so it must be `Send` and `Sync`. [`present()`][Self::present()] is only
+/// ever called from the editor's GUI thread, but the implementation must not block for long since
+/// that stalls the GUI. Copy the pixels somewhere else if they're consumed on another thread.
+pub trait SlintRenderTarget: Send + Sync {
+    /// Receive a finished frame. `pixels` contains `width * height` pixels with rows stored from
+    /// top to bottom without any padding. Each pixel is a `u32` in `0x00RRGGBB` format, which is
+    /// the same format softbuffer uses, and the unused top byte is always zero. The size is the
+    /// window's size in physical pixels, so on a 2x display it's twice the editor's logical size.
+    ///
+    /// `pixels` is only valid for the duration of the call. With
+    /// [`SlintEditorOptions::redraw_on_demand`][crate::SlintEditorOptions::redraw_on_demand]
+    /// enabled, this is only called for frames that changed.
+    fn present(&self, pixels: &[u32], width: u32, height: u32);
+}
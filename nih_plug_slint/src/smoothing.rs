@@ -0,0 +1,239 @@
+//! Smoothed values for meters and other visualizations, and smoothed pointer input.
+
+use crossbeam::atomic::AtomicCell;
+use parking_lot::Mutex;
+use slint::LogicalPosition;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A value that's written by the audio thread and read by the GUI, with exponential smoothing
+/// applied on the GUI side so meters glide between updates instead of jumping. The audio thread
+/// sets a target with [`set_target()`][Self::set_target()], which is lock-free and
+/// allocation-free. The GUI then calls [`next()`][Self::next()] once per frame to advance the
+/// smoothed value toward the target based on the time that has passed since the previous call,
+/// so the smoothing behaves the same regardless of the frame rate.
+///
+/// The value can be cloned, and all clones share the same state.
+///
+/// # Example
+///
+/// ```ignore
+/// // In the plugin, with `peak_meter: SlintSmoothedValue` stored on the plugin struct:
+/// self.peak_meter.set_target(peak_level);
+///
+/// // In the component factory:
+/// let timer = slint::Timer::default();
+/// timer.start(slint::TimerMode::Repeated, Duration::from_millis(16), {
+///     let ui = SlintComponentRef::new(&ui);
+///     let peak_meter = peak_meter.clone();
+///     move || {
+///         ui.with_component(|ui| ui.set_peak_level(peak_meter.next()));
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct SlintSmoothedValue {
+    inner: Arc<SmoothedValueInner>,
+}
+
+struct SmoothedValueInner {
+    /// The value the smoothed value is moving toward. Written by the audio thread.
+    target: AtomicCell<f32>,
+    /// The time it takes for the smoothed value to cover roughly 63% of the distance to the target.
+    smoothing_time: AtomicCell<Duration>,
+    /// The current smoothed value and the time it was last advanced. Only used from the GUI.
+    state: Mutex<SmoothingState>,
+}
+
+struct SmoothingState {
+    current: f32,
+    last_update: Option<Instant>,
+}
+
+impl SlintSmoothedValue {
+    /// Create a new smoothed value starting at `initial`. `smoothing_time` is the time constant
+    /// of the exponential smoothing, i.e. the time it takes to cover roughly 63% of the distance
+    /// to a new target. A zero duration disables smoothing.
+    pub fn new(initial: f32, smoothing_time: Duration) -> Self {
+        Self {
+            inner: Arc::new(SmoothedValueInner {
+                target: AtomicCell::new(initial),
+                smoothing_time: AtomicCell::new(smoothing_time),
+                state: Mutex::new(SmoothingState {
+                    current: initial,
+                    last_update: None,
+                }),
+            }),
+        }
+    }
+
+    /// Set the value to smooth toward. This is safe to call from the audio thread.
+    pub fn set_target(&self, target: f32) {
+        self.inner.target.store(target);
+    }
+
+    /// The value the smoothed value is moving toward.
+    pub fn target(&self) -> f32 {
+        self.inner.target.load()
+    }
+
+    /// Change the smoothing time. See [`new()`][Self::new()].
+    pub fn set_smoothing_time(&self, smoothing_time: Duration) {
+        self.inner.smoothing_time.store(smoothing_time);
+    }
+
+    /// Advance the smoothed value toward the target based on the time since the last call, and
+    /// return the new value. Call this once per frame from the GUI thread.
+    pub fn next(&self) -> f32 {
+        let now = Instant::now();
+        let mut state = self.inner.state.lock();
+        let elapsed = state
+            .last_update
+            .map(|last_update| now.duration_since(last_update))
+            .unwrap_or_default();
+        state.last_update = Some(now);
+
+        state.current = advance(
+            state.current,
+            self.target(),
+            elapsed,
+            self.inner.smoothing_time.load(),
+        );
+        state.current
+    }
+
+    /// The current smoothed value without advancing it.
+    pub fn current(&self) -> f32 {
+        self.inner.state.lock().current
+    }
+
+    /// Immediately jump to `value`, setting both the target and the smoothed value.
+    pub fn reset(&self, value: f32) {
+        self.set_target(value);
+        let mut state = self.inner.state.lock();
+        state.current = value;
+        state.last_update = None;
+    }
+}
+
+/// Smooths the pointer positions reported during a drag, so drags stay fluid in hosts that deliver
+/// mouse events in bursts. See
+/// [`SlintEditorOptions::pointer_smoothing`][crate::SlintEditorOptions::pointer_smoothing].
+pub(crate) struct PointerSmoother {
+    /// The time constant of the smoothing.
+    smoothing_time: Duration,
+    /// The position that was last passed on to Slint.
+    current: LogicalPosition,
+    /// The latest position reported by the window system.
+    target: LogicalPosition,
+    /// When [`advance()`][Self::advance()] was last called.
+    last_update: Option<Instant>,
+}
+
+impl PointerSmoother {
+    /// The distance in logical pixels at which the smoothed position snaps to the target.
+    const SNAP_DISTANCE: f32 = 0.01;
+
+    pub(crate) fn new(smoothing_time: Duration) -> Self {
+        Self {
+            smoothing_time,
+            current: LogicalPosition::default(),
+            target: LogicalPosition::default(),
+            last_update: None,
+        }
+    }
+
+    /// Jump to `position` without smoothing, for instance when a drag starts.
+    pub(crate) fn reset(&mut self, position: LogicalPosition) {
+        self.current = position;
+        self.target = position;
+    }
+
+    /// Set the position to smooth toward.
+    pub(crate) fn set_target(&mut self, position: LogicalPosition) {
+        self.target = position;
+    }
+
+    /// Move the smoothed position toward the target based on the time since the last call.
+    /// Returns the new position, or `None` if it was already at the target.
+    pub(crate) fn advance(&mut self, now: Instant) -> Option<LogicalPosition> {
+        let elapsed = self
+            .last_update
+            .map(|last_update| now.duration_since(last_update))
+            .unwrap_or_default();
+        self.last_update = Some(now);
+        if self.current == self.target {
+            return None;
+        }
+
+        let x = advance(self.current.x, self.target.x, elapsed, self.smoothing_time);
+        let y = advance(self.current.y, self.target.y, elapsed, self.smoothing_time);
+        self.current = if (self.target.x - x).hypot(self.target.y - y) < Self::SNAP_DISTANCE {
+            self.target
+        } else {
+            LogicalPosition::new(x, y)
+        };
+
+        Some(self.current)
+    }
+
+    /// Jump to the target, for instance when a drag ends. Returns the target, or `None` if the
+    /// smoothed position was already there.
+    pub(crate) fn finish(&mut self) -> Option<LogicalPosition> {
+        if self.current == self.target {
+            return None;
+        }
+
+        self.current = self.target;
+        Some(self.current)
+    }
+}
+
+/// Move `current` toward `target` using a one-pole exponential filter with a time constant of
+/// `smoothing_time`, after `elapsed` time has passed.
+fn advance(current: f32, target: f32, elapsed: Duration, smoothing_time: Duration) -> f32 {
+    if smoothing_time.is_zero() {
+        return target;
+    }
+
+    let coefficient = 1.0 - (-elapsed.as_secs_f32() / smoothing_time.as_secs_f32()).exp();
+    current + (target - current) * coefficient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_smoothing() {
+        let smoothing_time = Duration::from_millis(100);
+
+        assert_eq!(advance(0.0, 1.0, Duration::ZERO, smoothing_time), 0.0);
+        let after_one_time_constant = advance(0.0, 1.0, smoothing_time, smoothing_time);
+        assert!((after_one_time_constant - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+        assert_eq!(
+            advance(0.0, 1.0, Duration::from_millis(1), Duration::ZERO),
+            1.0
+        );
+    }
+
+    #[test]
+    fn pointer_smoothing_converges_and_finishes_at_the_target() {
+        let start = Instant::now();
+        let mut smoother = PointerSmoother::new(Duration::from_millis(10));
+        smoother.reset(LogicalPosition::new(0.0, 0.0));
+        assert_eq!(smoother.advance(start), None);
+
+        // A burst of events only sets the target, the frames then glide toward it
+        smoother.set_target(LogicalPosition::new(50.0, 0.0));
+        smoother.set_target(LogicalPosition::new(100.0, 0.0));
+        let first = smoother.advance(start + Duration::from_millis(10)).unwrap();
+        assert!(first.x > 0.0 && first.x < 100.0);
+        let second = smoother.advance(start + Duration::from_millis(20)).unwrap();
+        assert!(second.x > first.x && second.x < 100.0);
+
+        assert_eq!(smoother.finish(), Some(LogicalPosition::new(100.0, 0.0)));
+        assert_eq!(smoother.finish(), None);
+        assert_eq!(smoother.advance(start + Duration::from_millis(30)), None);
+    }
+}
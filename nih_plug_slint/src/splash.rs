@@ -0,0 +1,130 @@
+//! A placeholder frame shown while the plugin's Slint component is being created.
+
+use slint::{Rgb8Pixel, Rgba8Pixel, SharedPixelBuffer};
+
+/// A simple frame drawn into the editor's window before the component factory runs. Set it as
+/// [`SlintEditorOptions::splash`][crate::SlintEditorOptions::splash].
+///
+/// Without it, the window stays black or shows whatever the host drew there until the first
+/// frame of the component has been rendered. For editors that load a lot of assets in their
+/// component factory, a background color matching the editor and the plugin's logo make opening
+/// the editor feel much faster. The splash frame is replaced by the component's first frame.
+///
+/// This is drawn directly into the window's pixels without Slint, so it can't contain text
+/// other than what's part of the logo image. The splash frame is skipped in the rare case that
+/// the window's scale factor can't be determined before the window opens, since it would be drawn
+/// at the wrong size.
+///
+/// # Example
+///
+/// ```ignore
+/// let logo = image::load_from_memory(include_bytes!("../assets/logo.png"))?.into_rgba8();
+/// let options = SlintEditorOptions {
+///     splash: Some(SlintSplash {
+///         background: Rgb8Pixel::new(0x1e, 0x1e, 0x24),
+///         logo: Some(SharedPixelBuffer::clone_from_slice(
+///             logo.as_raw(),
+///             logo.width(),
+///             logo.height(),
+///         )),
+///     }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct SlintSplash {
+    /// The color filling the window.
+    pub background: Rgb8Pixel,
+    /// An image drawn in the center of the window, blended onto the background according to its
+    /// alpha channel. Its size is in logical pixels, so it's enlarged on HiDPI displays. It's
+    /// shrunk to fit if the window is too small for it.
+    pub logo: Option<SharedPixelBuffer<Rgba8Pixel>>,
+}
+
+impl PartialEq for SlintSplash {
+    fn eq(&self, other: &Self) -> bool {
+        let logo_eq = match (&self.logo, &other.logo) {
+            (Some(a), Some(b)) => a.size() == b.size() && a.as_slice() == b.as_slice(),
+            (None, None) => true,
+            _ => false,
+        };
+
+        self.background == other.background && logo_eq
+    }
+}
+
+impl SlintSplash {
+    /// Draw the splash frame into a `width * height` frame at the given scale factor.
+    pub(crate) fn draw(&self, pixels: &mut [Rgb8Pixel], width: u32, height: u32, scale: f32) {
+        pixels.fill(self.background);
+
+        let Some(logo) = &self.logo else {
+            return;
+        };
+        if logo.width() == 0 || logo.height() == 0 || width == 0 || height == 0 {
+            return;
+        }
+
+        // The logo keeps its aspect ratio when it's shrunk to fit the window
+        let fit = (width as f32 / logo.width() as f32).min(height as f32 / logo.height() as f32);
+        let scale = scale.min(fit);
+        let logo_width = ((logo.width() as f32 * scale).round() as u32).clamp(1, width);
+        let logo_height = ((logo.height() as f32 * scale).round() as u32).clamp(1, height);
+        let origin_x = (width - logo_width) / 2;
+        let origin_y = (height - logo_height) / 2;
+
+        let logo_pixels = logo.as_slice();
+        for y in 0..logo_height {
+            let src_y = (y as u64 * logo.height() as u64 / logo_height as u64) as usize;
+            for x in 0..logo_width {
+                let src_x = (x as u64 * logo.width() as u64 / logo_width as u64) as usize;
+                let src = logo_pixels[src_y * logo.width() as usize + src_x];
+                let dst_index = (origin_y + y) as usize * width as usize + (origin_x + x) as usize;
+                pixels[dst_index] = blend(src, pixels[dst_index]);
+            }
+        }
+    }
+}
+
+/// Blend `src` onto `dst` according to `src`'s alpha.
+fn blend(src: Rgba8Pixel, dst: Rgb8Pixel) -> Rgb8Pixel {
+    let alpha = src.a as u32;
+    let mix =
+        |src: u8, dst: u8| ((src as u32 * alpha + dst as u32 * (255 - alpha) + 127) / 255) as u8;
+
+    Rgb8Pixel::new(mix(src.r, dst.r), mix(src.g, dst.g), mix(src.b, dst.b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logo_is_centered_and_scaled() {
+        let mut logo = SharedPixelBuffer::<Rgba8Pixel>::new(2, 2);
+        let opaque_white = Rgba8Pixel::new(255, 255, 255, 255);
+        logo.make_mut_slice().fill(opaque_white);
+        let background = Rgb8Pixel::new(10, 20, 30);
+        let splash = SlintSplash {
+            background,
+            logo: Some(logo),
+        };
+
+        // At a scale factor of 2 the 2x2 logo covers the center 4x4 pixels of the 8x8 frame
+        let mut pixels = vec![Rgb8Pixel::default(); 64];
+        splash.draw(&mut pixels, 8, 8, 2.0);
+        let white = Rgb8Pixel::new(255, 255, 255);
+        for y in 0..8 {
+            for x in 0..8 {
+                let in_logo = (2..6).contains(&x) && (2..6).contains(&y);
+                let expected = if in_logo { white } else { background };
+                assert_eq!(pixels[y * 8 + x], expected, "pixel ({x}, {y})");
+            }
+        }
+
+        // The logo is shrunk to fit a smaller frame
+        let mut pixels = vec![Rgb8Pixel::default(); 3];
+        splash.draw(&mut pixels, 3, 1, 2.0);
+        assert_eq!(pixels, [background, white, background]);
+    }
+}
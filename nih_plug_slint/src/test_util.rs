@@ -0,0 +1,100 @@
+//! Utilities for testing Slint editors without opening a window. Enabled with the `test-util`
+//! feature.
+
+use crate::platform::{ensure_slint_platform, set_pending_window};
+use slint::platform::software_renderer::{MinimalSoftwareWindow, RepaintBufferType};
+use slint::platform::WindowEvent;
+use slint::{ComponentHandle, PhysicalSize, Rgb8Pixel};
+
+// Exported for the `pixel_buffer_resize` benchmark
+#[doc(hidden)]
+pub use crate::pixel_buffer::PixelBuffer;
+
+/// Render a component headlessly and return its pixels, for golden image tests in CI. This uses
+/// the same software renderer as the editor, but without baseview or softbuffer, so it works on
+/// machines without a display.
+///
+/// `create_component` is called to create the component, just like the component factory passed
+/// to [`create_slint_editor()`][crate::create_slint_editor()]. `width` and `height` are the
+/// window's logical size, and `scale_factor` is the display's scale factor.
+///
+/// The result contains `physical_width * physical_height` pixels with four bytes per pixel in
+/// RGBA order, stored row by row starting at the top left corner without any padding. The
+/// physical size is the logical size multiplied by `scale_factor`, rounded to the nearest pixel.
+/// Like in the editor, the window is rendered as an opaque image, so the alpha channel is always
+/// 255.
+///
+/// Slint's state is stored per thread, and the Slint platform is set up for every thread this is
+/// called from, so this can be used from tests running in parallel.
+///
+/// # Example
+///
+/// ```ignore
+/// #[test]
+/// fn background_is_dark() {
+///     let pixels = render_to_rgba(|| PluginUI::new().unwrap(), 200, 100, 1.0);
+///     assert_eq!(&pixels[0..4], &[0x20, 0x20, 0x20, 0xff]);
+/// }
+/// ```
+pub fn render_to_rgba<C: ComponentHandle>(
+    create_component: impl FnOnce() -> C,
+    width: u32,
+    height: u32,
+    scale_factor: f32,
+) -> Vec<u8> {
+    ensure_slint_platform().expect("Could not set up the Slint platform");
+
+    let physical_width = (width as f32 * scale_factor).round() as u32;
+    let physical_height = (height as f32 * scale_factor).round() as u32;
+
+    let window = MinimalSoftwareWindow::new(RepaintBufferType::NewBuffer);
+    window.dispatch_event(WindowEvent::ScaleFactorChanged { scale_factor });
+    window.set_size(PhysicalSize::new(physical_width, physical_height));
+
+    set_pending_window(window.clone());
+    let component = create_component();
+    component.show().expect("Could not show the component");
+    slint::platform::update_timers_and_animations();
+
+    let mut pixels = vec![Rgb8Pixel::default(); physical_width as usize * physical_height as usize];
+    window.request_redraw();
+    window.draw_if_needed(|renderer| {
+        renderer.render(&mut pixels, physical_width as usize);
+    });
+    component.hide().expect("Could not hide the component");
+
+    pixels
+        .iter()
+        .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, 255])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    slint::slint! {
+        export component GoldenTest inherits Window {
+            background: #ff0000;
+
+            Rectangle {
+                x: 50px;
+                y: 0px;
+                width: 50px;
+                height: 50px;
+                background: #0000ff;
+            }
+        }
+    }
+
+    #[test]
+    fn renders_component() {
+        let pixels = render_to_rgba(|| GoldenTest::new().unwrap(), 100, 50, 2.0);
+        assert_eq!(pixels.len(), 200 * 100 * 4);
+
+        // The left half is the window's background, and the right half is the rectangle
+        assert_eq!(&pixels[0..4], &[0xff, 0x00, 0x00, 0xff]);
+        let right = (10 * 200 + 150) * 4;
+        assert_eq!(&pixels[right..right + 4], &[0x00, 0x00, 0xff, 0xff]);
+    }
+}
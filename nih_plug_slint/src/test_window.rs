@@ -0,0 +1,23 @@
+//! A fixture for the crate's own tests that need a live Slint component.
+
+use crate::platform::{ensure_slint_platform, set_pending_window};
+use slint::platform::software_renderer::{MinimalSoftwareWindow, RepaintBufferType};
+use slint::{ComponentHandle, PhysicalSize};
+use std::rc::Rc;
+
+/// Set up the Slint platform on the current thread, create a component with `create_component`
+/// in a new 100x100 software window, and show it. Returns the window, for dispatching events to
+/// the component, along with the component itself. The window keeps the component alive until
+/// it's hidden.
+pub(crate) fn show_in_test_window<C: ComponentHandle>(
+    create_component: impl FnOnce() -> C,
+) -> (Rc<MinimalSoftwareWindow>, C) {
+    ensure_slint_platform().unwrap();
+    let window = MinimalSoftwareWindow::new(RepaintBufferType::NewBuffer);
+    set_pending_window(window.clone());
+    let component = create_component();
+    component.show().unwrap();
+    window.set_size(PhysicalSize::new(100, 100));
+
+    (window, component)
+}
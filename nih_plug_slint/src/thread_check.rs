@@ -0,0 +1,88 @@
+//! Checks for using Slint components from the wrong thread. Enabled with the `thread-checks`
+//! feature in debug builds.
+
+/// The thread a Slint component or window was created on. Slint components can only be used from
+/// that thread. Accessing them from anywhere else either silently does nothing, as with
+/// [`slint::Weak::upgrade()`], or fails deep inside of Slint with a message that doesn't point
+/// back at the plugin's code. With the `thread-checks` feature enabled in a debug build,
+/// [`check()`][Self::check()] panics with a message naming both threads instead. Otherwise this is
+/// zero-sized and the checks compile to nothing.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ThreadAffinity {
+    #[cfg(all(feature = "thread-checks", debug_assertions))]
+    thread: Option<std::thread::ThreadId>,
+}
+
+impl ThreadAffinity {
+    /// Bind to the current thread.
+    pub(crate) fn current() -> Self {
+        Self {
+            #[cfg(all(feature = "thread-checks", debug_assertions))]
+            thread: Some(std::thread::current().id()),
+        }
+    }
+
+    /// Don't check anything, for when the creating thread isn't known.
+    pub(crate) fn unknown() -> Self {
+        Self {
+            #[cfg(all(feature = "thread-checks", debug_assertions))]
+            thread: None,
+        }
+    }
+
+    /// Panic if this is called from a thread other than the one this was created on. `what`
+    /// describes what's being accessed, for the panic message.
+    #[cfg_attr(
+        not(all(feature = "thread-checks", debug_assertions)),
+        allow(unused_variables)
+    )]
+    pub(crate) fn check(&self, what: &str) {
+        #[cfg(all(feature = "thread-checks", debug_assertions))]
+        if let Some(thread) = self.thread {
+            let current = std::thread::current();
+            assert!(
+                current.id() == thread,
+                "{what} was used from thread {:?} ({}), but it was created on thread {:?}. Slint \
+                 components can only be used from the GUI thread that created them. To update the \
+                 component from another thread, use SlintState::update_component() instead.",
+                current.id(),
+                current.name().unwrap_or("unnamed"),
+                thread,
+            );
+        }
+    }
+}
+
+/// Panic if the component returned by the component factory was not created by the factory on the
+/// current thread, which is the editor's window thread. Such a component, for instance one that
+/// was kept around from an earlier window, doesn't use the editor's window and may belong to a
+/// different thread. `created_here` is whether the component used the window set up for it. This
+/// only checks anything with the `thread-checks` feature enabled in a debug build.
+#[cfg_attr(
+    not(all(feature = "thread-checks", debug_assertions)),
+    allow(unused_variables)
+)]
+pub(crate) fn check_factory_component(created_here: bool) {
+    #[cfg(all(feature = "thread-checks", debug_assertions))]
+    assert!(
+        created_here,
+        "The component factory returned a component it didn't create on the editor's window \
+         thread {:?}. Slint components can only be used from the thread that created them, so \
+         the factory needs to create a new component every time it's called.",
+        std::thread::current().id(),
+    );
+}
+
+#[cfg(all(test, feature = "thread-checks", debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn other_thread_panics() {
+        let affinity = ThreadAffinity::current();
+        affinity.check("The component");
+
+        let result = std::thread::spawn(move || affinity.check("The component")).join();
+        assert!(result.is_err());
+    }
+}
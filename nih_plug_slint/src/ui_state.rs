@@ -0,0 +1,48 @@
+//! UI-only state that lives in the component instead of in the plugin's parameters.
+
+use crate::SlintComponent;
+
+/// A component with UI-only state, like the selected tab, collapsed panels, or other toggles that
+/// aren't parameters. Implement this and call
+/// [`SlintState::enable_ui_state()`][crate::SlintState::enable_ui_state()] to have that state
+/// saved in the editor's [`SlintState`][crate::SlintState] and restored when the component is
+/// created again, whether that's because the editor was reopened, the component was rebuilt, or
+/// the host restored the plugin's state.
+///
+/// The state is an opaque string, so any format works. Serializing a small struct to JSON is a
+/// good default. Restoring should tolerate states saved by older versions of the plugin and
+/// states it can't parse, since those are restored as well.
+///
+/// The same methods also work for A/B comparisons of UI state. Call them directly on the
+/// component, for instance from its callbacks or through
+/// [`SlintState::update_component()`][crate::SlintState::update_component()], and keep the
+/// snapshots next to the plugin's A/B parameter snapshots.
+///
+/// # Example
+///
+/// ```ignore
+/// impl SlintUiState for MyPluginUI {
+///     fn save_ui_state(&self) -> String {
+///         self.get_selected_tab().to_string()
+///     }
+///
+///     fn restore_ui_state(&self, state: &str) {
+///         if let Ok(tab) = state.parse() {
+///             self.set_selected_tab(tab);
+///         }
+///     }
+/// }
+///
+/// // Before creating the editor:
+/// self.params.editor_state.enable_ui_state::<MyPluginUI>();
+/// ```
+pub trait SlintUiState: SlintComponent {
+    /// Serialize the component's UI-only state. This is called on the GUI thread before the
+    /// component is dropped.
+    fn save_ui_state(&self) -> String;
+
+    /// Restore a state created by [`save_ui_state()`][Self::save_ui_state()]. This is called on
+    /// the GUI thread right after the component is created, and whenever the state is replaced
+    /// while the editor is open.
+    fn restore_ui_state(&self, state: &str);
+}
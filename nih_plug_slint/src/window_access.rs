@@ -0,0 +1,48 @@
+//! Controlled access to the editor's baseview window.
+
+/// Window-level operations on the open editor's window, for host integrations that need them.
+/// See [`SlintState::with_window()`][crate::SlintState::with_window()].
+///
+/// Baseview's window can only be used from the GUI thread while it handles an event or a frame,
+/// and the [`WindowHandle`][baseview::WindowHandle] owned by the host's editor handle is only
+/// `Send` because the host promises to drop it on the GUI thread. Neither can safely be handed
+/// out to plugin code, so this wraps the window for the duration of a single callback instead.
+pub struct SlintWindowAccess<'a, 'b> {
+    window: &'a mut baseview::Window<'b>,
+    close_requested: bool,
+}
+
+impl<'a, 'b> SlintWindowAccess<'a, 'b> {
+    pub(crate) fn new(window: &'a mut baseview::Window<'b>) -> Self {
+        Self {
+            window,
+            close_requested: false,
+        }
+    }
+
+    /// Whether the window currently has keyboard focus.
+    pub fn has_focus(&mut self) -> bool {
+        self.window.has_focus()
+    }
+
+    /// Give the window keyboard focus. Some hosts only pass key presses to the plugin's window
+    /// after it was clicked, so this can help when the editor should react to the keyboard right
+    /// after it opens.
+    pub fn focus(&mut self) {
+        self.window.focus();
+    }
+
+    /// Close the window once the callback returns. The editor goes through the same steps as when
+    /// the host closes it, so the open changed callback and the UI state are handled as usual.
+    /// The host still owns the editor and will try to close it again later, which does nothing.
+    /// Until the host opens the editor again, the plugin's area in the host's window stays empty,
+    /// so this should only be used in coordination with the host.
+    pub fn close(&mut self) {
+        self.close_requested = true;
+    }
+
+    /// Whether [`close()`][Self::close()] was called.
+    pub(crate) fn close_requested(&self) -> bool {
+        self.close_requested
+    }
+}
@@ -1,32 +1,48 @@
 //! Baseview WindowHandler implementation for Slint.
 
-use crate::editor::ParamChangedCallback;
-use crate::event_translation::translate_event;
-use crate::platform::set_pending_window;
-use crate::{SlintMouseControl, SlintState};
+use crate::bindings::sync_from_host;
+use crate::editor::{ParamChangedCallback, SpawnToken};
+use crate::error::SlintEditorError;
+use crate::error_panel::ErrorPanel;
+use crate::event_translation::{
+    is_modifier_key, modifier_changes, scroll_delta, set_pointer_position, translate_event,
+    translate_mapped_key_event,
+};
+use crate::logging::{debug_log, error_log, info_log, trace_log, warn_log};
+use crate::options::simulated_present_failures;
+use crate::overlay::draw_software_cursor;
+use crate::pixel_buffer::PixelBuffer;
+use crate::platform::{
+    ensure_slint_platform, set_click_interval, set_pending_window, take_pending_window,
+};
+use crate::render_target::SlintRenderTarget;
+use crate::smoothing::PointerSmoother;
+use crate::splash::SlintSplash;
+use crate::thread_check::check_factory_component;
+use crate::window_access::SlintWindowAccess;
+use crate::window_opacity::set_window_opacity;
+use crate::{
+    Modifiers, SlintDirtyRegion, SlintEditorOptions, SlintMouseControl, SlintOverlayFrame,
+    SlintRenderMode, SlintScaleInfo, SlintScaleSource, SlintState, UnboundedMovement,
+    UnboundedRegion,
+};
 use nih_plug::prelude::GuiContext;
-use slint::platform::software_renderer::MinimalSoftwareWindow;
+use slint::platform::software_renderer::{MinimalSoftwareWindow, RepaintBufferType};
 use slint::platform::WindowAdapter;
-use slint::{LogicalPosition, PhysicalSize};
+use slint::{ComponentHandle, LogicalPosition, PhysicalSize, Rgb8Pixel, SharedPixelBuffer};
+use std::any::Any;
 use std::cell::RefCell;
 use std::num::{NonZeroU32, NonZeroIsize};
 use std::ptr::NonNull;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::io::Write;
+use std::time::{Duration, Instant};
 
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/nih_plug_slint_debug.log")
-    {
-        let _ = writeln!(file, "{}", msg);
-    }
-}
-
-/// Install a panic hook that logs panic details to our debug log
+/// Install a panic hook that logs panic details to our debug log. The hook that was installed
+/// before, whether that's the default hook or a crash reporter set up by the host or the plugin, is
+/// still called afterwards. This only happens once per process, no matter how often editors are
+/// opened and closed.
 fn install_panic_hook() {
     use std::sync::Once;
     static HOOK_INSTALLED: Once = Once::new();
@@ -34,13 +50,7 @@ fn install_panic_hook() {
     HOOK_INSTALLED.call_once(|| {
         let original_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |panic_info| {
-            let msg = if let Some(s) = panic_info.payload().downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = panic_info.payload().downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "Unknown panic payload".to_string()
-            };
+            let msg = panic_message(panic_info.payload());
 
             let location = if let Some(loc) = panic_info.location() {
                 format!("{}:{}:{}", loc.file(), loc.line(), loc.column())
@@ -48,258 +58,974 @@ fn install_panic_hook() {
                 "unknown location".to_string()
             };
 
-            debug_log(&format!("PANIC DETAILS: {} at {}", msg, location));
+            error_log!("PANIC DETAILS: {} at {}", msg, location);
 
-            // Don't call original hook - we're handling panics ourselves
-            let _ = &original_hook;
+            original_hook(panic_info);
         }));
     });
 }
 
+/// How many frames we'll wait for baseview's initial `Resized` event before painting anyway. On
+/// Windows and Linux the real scale factor is only known once that event arrives, and painting
+/// before then would cause a visible resize pop on HiDPI displays.
+const MAX_DEFERRED_INITIAL_FRAMES: u32 = 5;
+
+/// The longest delta time passed to [`SlintState::set_on_frame()`]'s callback. Longer gaps, for
+/// instance when the host blocked the GUI thread, would otherwise make animations jump.
+const MAX_FRAME_DELTA: Duration = Duration::from_millis(100);
+
+/// Extract the message from a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic payload".to_string()
+    }
+}
+
+/// Update Slint's timers and animations. A panic in one of the component's timer callbacks is
+/// caught and reported through [`SlintState::last_error()`] here, so it doesn't take the rest of
+/// the frame or event with it.
+fn update_timers_and_animations(slint_state: &SlintState) {
+    if let Err(payload) = std::panic::catch_unwind(slint::platform::update_timers_and_animations) {
+        let message = panic_message(payload.as_ref());
+        error_log!("A Slint timer callback panicked: {}", message);
+        slint_state.report_error(SlintEditorError::TimerCallback(message));
+    }
+}
+
+/// Invoke the parameter changed callback if any parameters changed since the last frame, and
+/// clear the flag again. However many changes the host sent in between, the callback runs at most
+/// once per frame, so dense automation doesn't update the component more often than it's drawn.
+/// Returns whether there were any changes.
+fn notify_param_changes<C>(
+    params_changed: &AtomicBool,
+    callback: Option<&ParamChangedCallback<C>>,
+    component: Option<&C>,
+) -> bool {
+    if !params_changed.swap(false, Ordering::AcqRel) {
+        return false;
+    }
+
+    trace_log!("Parameters changed since the last frame, calling the callback");
+    if let (Some(callback), Some(component)) = (callback, component) {
+        sync_from_host(|| callback(component));
+    }
+
+    true
+}
+
+/// The component shown in the window.
+enum LoadedComponent<C> {
+    /// The component returned by the plugin's component factory.
+    Ready(C),
+    /// The component factory panicked or its component could not be shown, so an error panel is
+    /// shown instead.
+    Failed(Option<ErrorPanel>),
+}
+
+impl<C: crate::SlintComponent> LoadedComponent<C> {
+    /// Run the component factory and show the result in `slint_window`. If that fails, then an
+    /// error panel with the failure's message is shown instead so a broken GUI doesn't take down
+    /// the host. If `catch_panics` is disabled, a panicking factory is not caught here.
+    fn create(
+        slint_state: &SlintState,
+        slint_window: &Rc<MinimalSoftwareWindow>,
+        component_factory: &dyn Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C,
+        gui_context: Arc<dyn GuiContext>,
+        mouse_control: SlintMouseControl,
+        catch_panics: bool,
+    ) -> Self {
+        // Slint's platform is set up per thread, and baseview may run the window on a thread of
+        // its own, like on X11. That thread only gets its platform here.
+        let error = match ensure_slint_platform() {
+            Ok(()) => {
+                // Set this window as the pending window so the component will use it
+                set_pending_window(slint_window.clone());
+
+                // Create the component - it will use our window via the platform
+                debug_log!("Creating Slint component...");
+                let result = if catch_panics {
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        component_factory(gui_context, mouse_control)
+                    }))
+                } else {
+                    Ok(component_factory(gui_context, mouse_control))
+                };
+                let error = match result {
+                    Ok(component) => {
+                        debug_log!("Slint component created, showing it...");
+                        match component.show() {
+                            Ok(()) => {
+                                // A component created by the factory on this thread uses the
+                                // pending window at the latest when it's shown
+                                check_factory_component(take_pending_window().is_none());
+                                return LoadedComponent::Ready(component);
+                            }
+                            Err(e) => format!("Could not show the component: {e}"),
+                        }
+                    }
+                    Err(payload) => panic_message(payload.as_ref()),
+                };
+
+                SlintEditorError::ComponentFactory(error)
+            }
+            Err(error) => error,
+        };
+
+        let message = error.to_string();
+        slint_state.report_error(error);
+
+        // The failed factory may not have consumed the pending window. Without a platform, the
+        // panel can't be shown either and the window stays blank.
+        set_pending_window(slint_window.clone());
+        let panel = ErrorPanel::new().and_then(|panel| {
+            panel.set_message(message.into());
+            panel.show().map(|()| panel)
+        });
+        match panel {
+            Ok(panel) => LoadedComponent::Failed(Some(panel)),
+            Err(e) => {
+                warn_log!("Could not show the error panel: {}", e);
+                LoadedComponent::Failed(None)
+            }
+        }
+    }
+
+    /// The plugin's component, if it was created successfully.
+    fn component(&self) -> Option<&C> {
+        match self {
+            LoadedComponent::Ready(component) => Some(component),
+            LoadedComponent::Failed(_) => None,
+        }
+    }
+
+    fn hide(&self) -> Result<(), slint::PlatformError> {
+        match self {
+            LoadedComponent::Ready(component) => component.hide(),
+            LoadedComponent::Failed(Some(panel)) => panel.hide(),
+            LoadedComponent::Failed(None) => Ok(()),
+        }
+    }
+}
+
 /// The Slint window handler that implements baseview's WindowHandler trait.
-pub struct SlintWindowHandler<C: slint::ComponentHandle + 'static> {
-    #[allow(dead_code)]
+pub struct SlintWindowHandler<C: crate::SlintComponent> {
     gui_context: Arc<dyn GuiContext>,
     slint_state: Arc<SlintState>,
 
     /// The Slint window adapter (MinimalSoftwareWindow)
     slint_window: Rc<MinimalSoftwareWindow>,
 
-    /// The Slint component instance
-    component: C,
+    /// The Slint component instance, or an error panel if creating it failed
+    component: LoadedComponent<C>,
+
+    /// Used to recreate the component when a rebuild is requested through
+    /// [`SlintState::request_rebuild()`].
+    component_factory: Arc<dyn Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync>,
+
+    /// Softbuffer context. This is `None` if softbuffer could not be set up for the window.
+    _sb_context: Option<softbuffer::Context<SoftbufferWindowHandleAdapter>>,
 
-    /// Softbuffer context
-    _sb_context: softbuffer::Context<SoftbufferWindowHandleAdapter>,
+    /// Softbuffer surface for blitting pixels. This is `None` if softbuffer could not be set up
+    /// for the window, in which case nothing is drawn.
+    sb_surface:
+        Option<softbuffer::Surface<SoftbufferWindowHandleAdapter, SoftbufferWindowHandleAdapter>>,
 
-    /// Softbuffer surface for blitting pixels
-    sb_surface: softbuffer::Surface<SoftbufferWindowHandleAdapter, SoftbufferWindowHandleAdapter>,
+    /// Detects a surface that can no longer be presented to, so it can be recreated.
+    present_health: PresentHealth,
 
-    /// Pixel buffer for rendering (RGBA format)
-    pixel_buffer: RefCell<Vec<slint::Rgb8Pixel>>,
+    /// Receives the frames instead of the softbuffer surface, if set. See [`SlintRenderTarget`].
+    render_target: Option<Arc<dyn SlintRenderTarget>>,
+
+    /// The frame passed to `render_target`, in softbuffer's format at the window's physical size.
+    target_frame: Vec<u32>,
+
+    /// Pixel buffer for rendering (RGB format). This is `render_width * render_height` pixels.
+    pixel_buffer: RefCell<PixelBuffer>,
+
+    /// A copy of `pixel_buffer` that the overlay set through [`SlintState::set_overlay()`] is
+    /// drawn on, so Slint's partial rendering never sees the overlay's pixels. This stays empty
+    /// until an overlay is set.
+    overlay_buffer: RefCell<PixelBuffer>,
 
     /// Physical dimensions of the window
     physical_width: u32,
     physical_height: u32,
 
+    /// The dimensions Slint renders at. These differ from the physical dimensions when
+    /// [`SlintEditorOptions::render_scale`] is not 1.0.
+    render_width: u32,
+    render_height: u32,
+
+    /// Additional options for the editor.
+    options: SlintEditorOptions,
+
     /// Current scaling factor
     scale_factor: f32,
 
+    /// Where the scale factor came from. Baseview's resize events can change the factor itself,
+    /// but not whether it was chosen by the host, the system, or the options.
+    scale_source: SlintScaleSource,
+
     /// Last known mouse position for events that don't include position
     last_mouse_position: RefCell<LogicalPosition>,
 
     /// Track whether a mouse button is currently pressed (for drag-outside-window handling)
     mouse_button_pressed: RefCell<bool>,
 
+    /// Whether the cursor is currently inside of the window. Used to send a deferred
+    /// `PointerExited` when a drag ends outside of the window.
+    cursor_in_window: RefCell<bool>,
+
     /// Mouse control for unbounded movement
     mouse_control: SlintMouseControl,
 
     /// Whether unbounded mouse movement is currently active
     unbounded_active: RefCell<bool>,
 
+    /// Where the current unbounded drag started, if the cursor should be restored to that
+    /// position once it ends.
+    unbounded_origin: Option<LogicalPosition>,
+
+    /// The position where the current unbounded drag started, and the last unconfined position
+    /// reported by baseview. Only used when [`SlintEditorOptions::unbounded_region`] confines the
+    /// virtual position. The raw position is kept at baseview's full precision, since baseview's
+    /// virtual positions can grow large during long drags.
+    unbounded_confinement: Option<(LogicalPosition, (f64, f64))>,
+
+    /// Smooths the pointer positions during drags, if enabled through
+    /// [`SlintEditorOptions::pointer_smoothing`].
+    pointer_smoother: Option<PointerSmoother>,
+
+    /// Whether a cursor should be drawn into the frame because the native cursor is hidden for
+    /// [`UnboundedMovement::SoftwareCursor`].
+    software_cursor: bool,
+
     /// Optional callback invoked when parameter values change from the host.
     on_param_values_changed: Option<ParamChangedCallback<C>>,
 
     /// Flag set by the editor when parameters change. Checked in on_frame.
     emit_parameters_changed_event: Arc<AtomicBool>,
+
+    /// The number of frames left to skip painting while waiting for the first `Resized` event.
+    /// Becomes `None` once that event has been handled or the wait timed out.
+    pending_initial_resize: Option<u32>,
+
+    /// Whether a frame was already painted from a resize event since the last `on_frame()`. Hosts
+    /// can send many resize events per frame during a live resize, and only the first of those is
+    /// painted right away.
+    painted_during_resize: bool,
+
+    /// When the previous frame started, for the delta time passed to
+    /// [`SlintState::set_on_frame()`]'s callback. `None` before the first frame and while the
+    /// editor is suspended.
+    last_frame: Option<Instant>,
+
+    /// Whether the component needs to be rendered with
+    /// [`SlintEditorOptions::static_content`] enabled. Set for the first frame and whenever
+    /// something that's allowed to change a static GUI happened.
+    static_render_pending: bool,
+
+    /// Whether the close sequence has already run. See [`Self::handle_close()`].
+    closed: bool,
+
+    /// Identifies this window among the ones spawned by the editor. Once the host spawns the
+    /// editor again, this window is stale and closes itself.
+    spawn_token: SpawnToken,
 }
 
-impl<C: slint::ComponentHandle + 'static> SlintWindowHandler<C> {
+impl<C: crate::SlintComponent> SlintWindowHandler<C> {
     pub fn new<F>(
         window: &mut baseview::Window<'_>,
         gui_context: Arc<dyn GuiContext>,
         slint_state: Arc<SlintState>,
         component_factory: Arc<F>,
         mouse_control: SlintMouseControl,
-        scale_factor: f32,
+        initial_scale_factor: Option<f32>,
+        scale_source: SlintScaleSource,
         on_param_values_changed: Option<ParamChangedCallback<C>>,
         emit_parameters_changed_event: Arc<AtomicBool>,
+        options: SlintEditorOptions,
+        render_target: Option<Arc<dyn SlintRenderTarget>>,
+        spawn_token: SpawnToken,
     ) -> Self
     where
         F: Fn(Arc<dyn GuiContext>, SlintMouseControl) -> C + Send + Sync + 'static,
     {
         install_panic_hook();
-        debug_log("SlintWindowHandler::new() starting");
+        info_log!("SlintWindowHandler::new() starting");
 
+        // If the scale factor couldn't be determined up front, the first `Resized` event corrects
+        // it before anything is painted
+        let scale_factor = initial_scale_factor.unwrap_or(1.0);
         let (unscaled_width, unscaled_height) = slint_state.size();
         let physical_width = (unscaled_width as f32 * scale_factor).round() as u32;
         let physical_height = (unscaled_height as f32 * scale_factor).round() as u32;
-        debug_log(&format!(
-            "Window size: {}x{} (scale: {})",
-            physical_width, physical_height, scale_factor
-        ));
-
-        // Create softbuffer context and surface
-        debug_log("Creating softbuffer context...");
-        let target = baseview_window_to_surface_target(window);
-        let sb_context = match softbuffer::Context::new(target.clone()) {
-            Ok(ctx) => {
-                debug_log("Softbuffer context created successfully");
-                ctx
-            }
-            Err(e) => {
-                debug_log(&format!("FAILED to create softbuffer context: {:?}", e));
-                panic!("could not get softbuffer context: {:?}", e);
-            }
-        };
+        let render_scale = options.effective_render_scale();
+        let render_width = render_dimension(physical_width, render_scale);
+        let render_height = render_dimension(physical_height, render_scale);
+        let pointer_smoother = options
+            .pointer_smoothing
+            .filter(|smoothing_time| !smoothing_time.is_zero())
+            .map(PointerSmoother::new);
+        debug_log!(
+            "Window size: {}x{} (scale: {}, render size: {}x{})",
+            physical_width, physical_height, scale_factor, render_width, render_height
+        );
 
-        debug_log("Creating softbuffer surface...");
-        let mut sb_surface = match softbuffer::Surface::new(&sb_context, target) {
-            Ok(surface) => {
-                debug_log("Softbuffer surface created successfully");
-                surface
-            }
-            Err(e) => {
-                debug_log(&format!("FAILED to create softbuffer surface: {:?}", e));
-                panic!("could not create softbuffer surface: {:?}", e);
+        // Create softbuffer context and surface. If that fails the editor keeps running without
+        // drawing anything, which is better than taking down the host. Frames for a custom render
+        // target don't need a surface at all.
+        let (sb_context, mut sb_surface) = if render_target.is_some() {
+            (None, None)
+        } else {
+            match create_softbuffer_surface(window, physical_width, physical_height) {
+                Ok((context, surface)) => (Some(context), Some(surface)),
+                Err(e) => {
+                    slint_state.report_error(e);
+                    (None, None)
+                }
             }
         };
 
-        debug_log("Resizing softbuffer surface...");
-        sb_surface
-            .resize(
-                NonZeroU32::new(physical_width).unwrap_or(NonZeroU32::new(1).unwrap()),
-                NonZeroU32::new(physical_height).unwrap_or(NonZeroU32::new(1).unwrap()),
-            )
-            .unwrap();
-        debug_log("Softbuffer surface resized");
+        // Component factories that load a lot of assets can take a while, so the splash frame is
+        // shown in the meantime. Like the component's frames, it's only drawn at the window's real
+        // scale factor, since it would otherwise appear at the wrong size until the first resize.
+        if let (Some(splash), Some(_)) = (&options.splash, initial_scale_factor) {
+            present_splash(
+                splash,
+                sb_surface.as_mut(),
+                render_target.as_deref(),
+                (physical_width, physical_height),
+                scale_factor,
+            );
+        }
 
         // Create the Slint window adapter
-        debug_log("Creating MinimalSoftwareWindow...");
-        let slint_window: Rc<MinimalSoftwareWindow> = MinimalSoftwareWindow::new(
-            slint::platform::software_renderer::RepaintBufferType::ReusedBuffer,
-        );
-        debug_log("MinimalSoftwareWindow created");
+        debug_log!("Creating MinimalSoftwareWindow...");
+        let slint_window: Rc<MinimalSoftwareWindow> =
+            MinimalSoftwareWindow::new(options.repaint_buffer_type());
+        debug_log!("MinimalSoftwareWindow created");
 
-        // Set the scale factor first so Slint knows how to interpret the physical size
+        // Set the scale factor first so Slint knows how to interpret the physical size. Slint
+        // renders at the render size, so the render scale needs to be included here.
         slint_window.dispatch_event(slint::platform::WindowEvent::ScaleFactorChanged {
-            scale_factor,
+            scale_factor: scale_factor * render_scale,
         });
 
         // Set the window size
-        slint_window.set_size(PhysicalSize::new(physical_width, physical_height));
-
-        // Set this window as the pending window so the component will use it
-        debug_log("Setting pending window...");
-        set_pending_window(slint_window.clone());
+        slint_window.set_size(PhysicalSize::new(render_width, render_height));
+        mouse_control.set_scale_factor(scale_factor);
+
+        let component = LoadedComponent::create(
+            &slint_state,
+            &slint_window,
+            component_factory.as_ref(),
+            Arc::clone(&gui_context),
+            mouse_control.clone(),
+            options.catch_factory_panics,
+        );
 
-        // Create the component - it will use our window via the platform
-        debug_log("Creating Slint component...");
-        let component = component_factory(Arc::clone(&gui_context), mouse_control.clone());
-        debug_log("Slint component created");
+        // This happens after the component factory ran so callbacks set from there see the
+        // surface's initial size as well
+        if sb_surface.is_some() || render_target.is_some() {
+            slint_state.notify_surface_changed(physical_width, physical_height);
+        }
+        if let Some(component) = component.component() {
+            slint_state.notify_content_scale(component, true);
+            slint_state.check_clipboard(component, true);
+            slint_state.notify_reduced_motion(component, true);
+            slint_state.restore_ui_state(component, true);
+            if let Some(focus_ring) = &options.focus_ring {
+                focus_ring.notify_focus_changed(component, true);
+            }
+        }
 
-        // Show the component in the window
-        debug_log("Showing Slint component...");
-        component.show().expect("Failed to show Slint component");
-        debug_log("Slint component shown");
+        // An opacity set before opening, for instance to fade the editor in, applies right away
+        slint_state.take_window_opacity_change();
+        let window_opacity = slint_state.window_opacity();
+        if window_opacity < 1.0 {
+            set_window_opacity(window, window_opacity);
+        }
 
         // Mark the window as active so Slint processes input events
         slint_window.dispatch_event(slint::platform::WindowEvent::WindowActiveChanged(true));
-        debug_log("Window marked as active");
+        debug_log!("Window marked as active");
 
         // Request an initial redraw
         slint_window.request_redraw();
 
         // Allocate pixel buffer
-        let pixel_count = (physical_width * physical_height) as usize;
-        let pixel_buffer = vec![slint::Rgb8Pixel::default(); pixel_count];
+        let pixel_buffer = PixelBuffer::new(render_width, render_height);
 
-        Self {
+        let handler = Self {
             gui_context,
             slint_state,
             slint_window,
             component,
+            component_factory,
             _sb_context: sb_context,
             sb_surface,
+            present_health: PresentHealth::new(simulated_present_failures()),
+            render_target,
+            target_frame: Vec::new(),
             pixel_buffer: RefCell::new(pixel_buffer),
+            overlay_buffer: RefCell::new(PixelBuffer::new(0, 0)),
             physical_width,
             physical_height,
+            render_width,
+            render_height,
+            options,
             scale_factor,
+            scale_source,
             last_mouse_position: RefCell::new(LogicalPosition::default()),
             mouse_button_pressed: RefCell::new(false),
+            cursor_in_window: RefCell::new(false),
             mouse_control,
             unbounded_active: RefCell::new(false),
+            unbounded_origin: None,
+            unbounded_confinement: None,
+            pointer_smoother,
+            software_cursor: false,
             on_param_values_changed,
             emit_parameters_changed_event,
-        }
+            pending_initial_resize: Some(MAX_DEFERRED_INITIAL_FRAMES),
+            painted_during_resize: false,
+            last_frame: None,
+            static_render_pending: true,
+            closed: false,
+            spawn_token,
+        };
+        handler.publish_scale_info();
+        handler.slint_state.set_render_mode(SlintRenderMode {
+            repaint_buffer: handler.options.repaint_buffer_type(),
+            redraw_on_demand: handler.options.redraw_on_demand,
+            static_content: handler.options.static_content,
+            custom_render_target: handler.render_target.is_some(),
+        });
+
+        handler
     }
 }
 
-impl<C: slint::ComponentHandle + 'static> SlintWindowHandler<C> {
+impl<C: crate::SlintComponent> SlintWindowHandler<C> {
+    /// Replace the softbuffer context and surface with new ones for the same window, after the old
+    /// surface stopped accepting frames. This happens when the display went to sleep or the GPU
+    /// was reset on some systems. If that fails, the error is reported and nothing is drawn
+    /// anymore.
+    fn recreate_softbuffer_surface(&mut self, window: &baseview::Window) {
+        warn_log!("Presenting keeps failing, recreating the softbuffer surface");
+
+        // The surface has to be dropped before its context
+        self.sb_surface = None;
+        self._sb_context = None;
+        match create_softbuffer_surface(window, self.physical_width, self.physical_height) {
+            Ok((context, surface)) => {
+                self._sb_context = Some(context);
+                self.sb_surface = Some(surface);
+
+                // The new surface starts out blank, so the next frame needs to be drawn in full
+                self.slint_state.invalidate();
+                info_log!("Recreated the softbuffer surface");
+            }
+            Err(e) => self.slint_state.report_error(e),
+        }
+    }
+
     /// Process any pending cursor control requests immediately.
     /// Called from both on_frame() and on_event() to ensure responsive cursor restoration.
     fn process_cursor_requests(&mut self, window: &mut baseview::Window) {
+        // A late request must not hide or confine the cursor after the window started closing
+        if self.closed {
+            return;
+        }
+
         if let Some((enable, restore_position)) = self.mouse_control.take_request() {
+            let restore_position = match self.options.unbounded_movement {
+                UnboundedMovement::Native => restore_position,
+                UnboundedMovement::NoRestore | UnboundedMovement::SoftwareCursor => false,
+                UnboundedMovement::Disabled => return,
+            };
+
             if enable && !*self.unbounded_active.borrow() {
-                window.enable_unbounded_mouse_movement(true, restore_position);
+                if self.options.unbounded_movement == UnboundedMovement::SoftwareCursor {
+                    window.set_mouse_cursor(baseview::MouseCursor::Hidden);
+                    self.software_cursor = true;
+                } else {
+                    window.enable_unbounded_mouse_movement(true, restore_position);
+                }
                 *self.unbounded_active.borrow_mut() = true;
+                let start = *self.last_mouse_position.borrow();
+                self.unbounded_origin = restore_position.then_some(start);
+                self.unbounded_confinement = (!self.software_cursor
+                    && self.options.unbounded_region != UnboundedRegion::Unlimited)
+                    .then_some((start, (start.x as f64, start.y as f64)));
             } else if !enable && *self.unbounded_active.borrow() {
-                window.enable_unbounded_mouse_movement(false, false);
-                *self.unbounded_active.borrow_mut() = false;
+                self.disable_unbounded_movement(window);
             }
         }
     }
 
+    /// Carry out a resize requested through [`SlintState::request_resize()`], if the host agrees
+    /// to it. The host asks the editor for its size while handling the request, so the new size
+    /// is stored first and restored if the host declines.
+    fn apply_requested_resize(&mut self, window: &mut baseview::Window) {
+        let Some((width, height)) = self.slint_state.take_requested_size() else {
+            return;
+        };
+
+        let previous_size = self.slint_state.inner_logical_size();
+        if previous_size == (width, height) {
+            return;
+        }
+
+        self.slint_state.size.store((width, height));
+        if self.gui_context.request_resize() {
+            let (scaled_width, scaled_height) = self.slint_state.size();
+            debug_log!("Resizing the window to {}x{}", scaled_width, scaled_height);
+            window.resize(baseview::Size::new(scaled_width as f64, scaled_height as f64));
+        } else {
+            debug_log!("The host declined resizing the window to {}x{}", width, height);
+            self.slint_state.size.store(previous_size);
+        }
+    }
+
+    /// Store the currently held modifiers, and let the component know if they changed.
+    fn update_modifiers(&self, modifiers: Modifiers) {
+        if modifiers == self.mouse_control.modifiers() {
+            return;
+        }
+
+        self.mouse_control.set_modifiers(modifiers);
+        if let Some(component) = self.component.component() {
+            self.slint_state.notify_modifiers_changed(component, modifiers);
+        }
+    }
+
+    /// Stop unbounded mouse movement, restoring the cursor if that was requested when it started.
+    fn disable_unbounded_movement(&mut self, window: &mut baseview::Window) {
+        *self.unbounded_active.borrow_mut() = false;
+        self.unbounded_confinement = None;
+        if self.software_cursor {
+            window.set_mouse_cursor(baseview::MouseCursor::Default);
+            self.software_cursor = false;
+            return;
+        }
+
+        let origin = self.unbounded_origin.take();
+        window.enable_unbounded_mouse_movement(false, origin.is_some());
+
+        // Baseview warps the cursor back to where the drag started, but until the next cursor
+        // event we'd still consider the pointer to be at the last virtual position. Snapping our
+        // own position back to the exact starting position avoids the pointer being off by the
+        // rounding in the virtual position bookkeeping, which shows up as a small jump in the
+        // control under the cursor. Slint is only told about this once the drag is over, since
+        // moving the pointer mid-drag would move the dragged control too.
+        if let Some(origin) = origin {
+            *self.last_mouse_position.borrow_mut() = origin;
+            self.mouse_control.set_position(origin.x, origin.y);
+            if !*self.mouse_button_pressed.borrow() {
+                self.slint_window.dispatch_event(slint::platform::WindowEvent::PointerMoved {
+                    position: origin,
+                });
+            }
+        }
+    }
+
+    /// Drop the current component and create a new one from the component factory, showing it
+    /// in the existing window.
+    fn rebuild_component(&mut self) {
+        info_log!("Rebuilding Slint component...");
+        if let Some(component) = self.component.component() {
+            self.slint_state.save_ui_state(component);
+        }
+        if let Err(e) = self.component.hide() {
+            warn_log!("Failed to hide the old Slint component: {:?}", e);
+        }
+
+        let component = LoadedComponent::create(
+            &self.slint_state,
+            &self.slint_window,
+            self.component_factory.as_ref(),
+            Arc::clone(&self.gui_context),
+            self.mouse_control.clone(),
+            self.options.catch_factory_panics,
+        );
+        // The old component is dropped here
+        self.component = component;
+        self.static_render_pending = true;
+        self.slint_window
+            .dispatch_event(slint::platform::WindowEvent::WindowActiveChanged(true));
+
+        // The new component starts out with whatever defaults the factory gave it. Like for the
+        // per-frame updates, its bindings must not send these values back to the host.
+        if let (Some(callback), Some(component)) =
+            (&self.on_param_values_changed, self.component.component())
+        {
+            sync_from_host(|| callback(component));
+        }
+        if let Some(component) = self.component.component() {
+            self.slint_state.notify_content_scale(component, true);
+            self.slint_state.check_clipboard(component, true);
+            self.slint_state.notify_reduced_motion(component, true);
+            self.slint_state.restore_ui_state(component, true);
+            if let Some(focus_ring) = &self.options.focus_ring {
+                focus_ring.notify_focus_changed(component, true);
+            }
+        }
+
+        self.slint_window.request_redraw();
+        debug_log!("Slint component rebuilt");
+    }
+
+    /// Give the component a chance to clean up before the window is destroyed. This dispatches
+    /// `CloseRequested` so the component's `close-requested` handler runs, and then hides the
+    /// component. Closing can't be refused since the host decides when the editor closes, so the
+    /// component is hidden regardless of the handler's response. This only runs once, either when
+    /// baseview sends `WillClose` or when the handler gets dropped without that event.
+    fn handle_close(&mut self) {
+        if self.closed {
+            return;
+        }
+        self.closed = true;
+        info_log!("Closing the Slint window");
+
+        if let Err(e) = self
+            .slint_window
+            .try_dispatch_event(slint::platform::WindowEvent::CloseRequested)
+        {
+            warn_log!("Could not dispatch CloseRequested: {:?}", e);
+        }
+        // Let any timers started by the close handler fire
+        update_timers_and_animations(&self.slint_state);
+
+        // Any pending request is stale now. Unbounded movement itself is ended in `on_event()`
+        // once this returns, since that needs the window.
+        let _ = self.mouse_control.take_request();
+
+        // A window that was replaced by a newer one must not overwrite the state the newer window
+        // may have already saved or changed
+        if self.spawn_token.is_current() {
+            if let Some(component) = self.component.component() {
+                self.slint_state.save_ui_state(component);
+            }
+        }
+        if let Err(e) = self.component.hide() {
+            warn_log!("Failed to hide the Slint component: {:?}", e);
+        }
+    }
+
+    /// Let the plugin know about the current scale factors and sizes. See
+    /// [`SlintState::scale_info()`].
+    fn publish_scale_info(&self) {
+        let render_scale = self.options.effective_render_scale();
+        self.slint_state.set_scale_info(SlintScaleInfo {
+            scale_factor: self.scale_factor,
+            scale_source: self.scale_source,
+            user_scale_factor: self.slint_state.user_scale_factor(),
+            render_scale,
+            slint_scale_factor: self.scale_factor * render_scale,
+            logical_size: (
+                (self.physical_width as f32 / self.scale_factor).round() as u32,
+                (self.physical_height as f32 / self.scale_factor).round() as u32,
+            ),
+            physical_size: (self.physical_width, self.physical_height),
+            render_size: (self.render_width, self.render_height),
+        });
+    }
+
+    /// Clamp a logical position to the window's logical bounds.
+    fn clamp_to_window(&self, position: LogicalPosition) -> LogicalPosition {
+        let logical_width = self.physical_width as f32 / self.scale_factor;
+        let logical_height = self.physical_height as f32 / self.scale_factor;
+
+        LogicalPosition::new(
+            position.x.clamp(0.0, logical_width.max(0.0)),
+            position.y.clamp(0.0, logical_height.max(0.0)),
+        )
+    }
+
+    /// Force unbounded mouse movement to end, even if the component never asked for it. Used when
+    /// the mouse button was released outside of the window or the window lost focus mid-drag, in
+    /// which case the component may never see the release that would normally end the drag.
+    fn end_unbounded_movement(&mut self, window: &mut baseview::Window) {
+        if *self.unbounded_active.borrow() {
+            debug_log!("Ending unbounded mouse movement after the drag ended");
+            self.disable_unbounded_movement(window);
+        }
+
+        // Any enable request made during the drag is stale at this point
+        let _ = self.mouse_control.take_request();
+    }
+
     fn on_frame_inner(&mut self) {
-        // Check if parameters changed and invoke callback if needed
-        let flag_value = self.emit_parameters_changed_event.load(Ordering::Relaxed);
-        if flag_value {
-            debug_log(&format!("Flag is true, attempting compare_exchange"));
+        // Frames can still arrive after the window started closing
+        if self.closed || !has_live_component(&self.slint_window) {
+            return;
         }
-        if self
-            .emit_parameters_changed_event
-            .compare_exchange(true, false, Ordering::AcqRel, Ordering::Relaxed)
-            .is_ok()
+
+        // Everything, including parameter updates, is put on hold while the plugin has suspended
+        // the editor. Pending work is picked up again after resuming.
+        if self.slint_state.is_suspended() {
+            self.last_frame = None;
+            return;
+        }
+
+        let now = Instant::now();
+
+        // Smoothed drags move toward the latest pointer position once per frame
+        if let Some(position) = self
+            .pointer_smoother
+            .as_mut()
+            .and_then(|pointer_smoother| pointer_smoother.advance(now))
         {
-            debug_log("emit_parameters_changed_event flag was set, calling callback");
-            if let Some(callback) = &self.on_param_values_changed {
-                callback(&self.component);
-                debug_log("callback completed");
-            } else {
-                debug_log("no callback set");
+            dispatch_to_component(
+                &self.slint_window,
+                slint::platform::WindowEvent::PointerMoved { position },
+            );
+        }
+        let delta_time = self
+            .last_frame
+            .map(|last_frame| now.duration_since(last_frame).min(MAX_FRAME_DELTA))
+            .unwrap_or_default();
+        self.last_frame = Some(now);
+
+        if let Some(component) = self.component.component() {
+            self.slint_state.run_component_updates(component);
+            self.slint_state.run_content_fit(component);
+            self.slint_state.notify_content_scale(component, false);
+            self.slint_state.check_clipboard(component, false);
+            self.slint_state.notify_reduced_motion(component, false);
+            self.slint_state.restore_ui_state(component, false);
+            if let Some(focus_ring) = &self.options.focus_ring {
+                focus_ring.notify_focus_changed(component, false);
             }
+            self.slint_state
+                .notify_frame(component, delta_time.as_secs_f32());
+        }
+
+        if self
+            .slint_state
+            .rebuild_requested
+            .swap(false, Ordering::AcqRel)
+        {
+            self.rebuild_component();
+        }
+
+        // Changes made during a bulk update stay pending until it's done
+        if !self.slint_state.param_callbacks_suppressed()
+            && notify_param_changes(
+                &self.emit_parameters_changed_event,
+                self.on_param_values_changed.as_ref(),
+                self.component.component(),
+            )
+        {
+            self.static_render_pending = true;
         }
 
         // Update Slint timers and animations
-        slint::platform::update_timers_and_animations();
+        update_timers_and_animations(&self.slint_state);
 
-        // Request a redraw for animations
-        self.slint_window.request_redraw();
+        // Request a redraw for animations. In on-demand mode Slint requests redraws by itself
+        // whenever a property, animation, or the window's size changed, so idle frames cost
+        // nothing.
+        if !self.options.redraw_on_demand && !self.options.static_content {
+            self.slint_window.request_redraw();
+        }
+
+        // Don't paint anything until we know the window's actual size and scale factor
+        if let Some(frames_left) = self.pending_initial_resize {
+            if frames_left > 0 {
+                self.pending_initial_resize = Some(frames_left - 1);
+                return;
+            }
+
+            warn_log!("No initial resize received, painting with the assumed scale factor");
+            self.pending_initial_resize = None;
+        }
+
+        self.painted_during_resize = false;
+        self.paint();
+    }
+
+    /// Render the component if needed and present the result, along with any overlays.
+    fn paint(&mut self) {
+        // Render if needed. A full repaint is forced by temporarily switching the repaint buffer
+        // type, which clears the renderer's partial rendering cache.
+        let full_repaint = self
+            .slint_state
+            .invalidate_requested
+            .swap(false, Ordering::AcqRel);
+        if full_repaint {
+            self.slint_window.request_redraw();
+        }
+
+        // Static GUIs ignore Slint's own redraw requests and only render when we ask for it
+        let render = if self.options.static_content {
+            let render = std::mem::take(&mut self.static_render_pending) || full_repaint;
+            if render {
+                self.slint_window.request_redraw();
+            }
+            render
+        } else {
+            true
+        };
+
+        let mut frame_changed = false;
+        let mut dirty_bounds = None;
+        let drawn = render && self.slint_window.draw_if_needed(|renderer| {
+            if full_repaint {
+                renderer.set_repaint_buffer_type(RepaintBufferType::NewBuffer);
+            }
 
-        // Render if needed
-        self.slint_window.draw_if_needed(|renderer| {
             let mut pixel_buffer = self.pixel_buffer.borrow_mut();
-            renderer.render(&mut pixel_buffer, self.physical_width as usize);
+            let dirty_region =
+                renderer.render(pixel_buffer.pixels_mut(), self.render_width as usize);
+            let dirty_size = dirty_region.bounding_box_size();
+            frame_changed = dirty_size.width > 0 && dirty_size.height > 0;
+            dirty_bounds = Some((dirty_region.bounding_box_origin(), dirty_size));
+
+            if full_repaint {
+                renderer.set_repaint_buffer_type(self.options.repaint_buffer_type());
+            }
         });
 
-        // Blit to softbuffer
-        if let Ok(mut buffer) = self.sb_surface.buffer_mut() {
-            let pixel_buffer = self.pixel_buffer.borrow();
-            for (i, pixel) in pixel_buffer.iter().enumerate() {
-                // Convert RGBA8 to ARGB32 (softbuffer format)
-                // Format: 0x00RRGGBB (softbuffer on macOS uses 0RGB)
-                let r = pixel.r as u32;
-                let g = pixel.g as u32;
-                let b = pixel.b as u32;
-                buffer[i] = (r << 16) | (g << 8) | b;
+        // Draw the plugin's overlay and the software cursor, if any, on a copy of the rendered
+        // frame
+        let overlay = self.slint_state.overlay();
+        let has_overlay = overlay.is_some() || self.software_cursor;
+
+        // Let the plugin know whether the next frame is likely to change anything. Overlays are
+        // redrawn from scratch every frame, so they always count as a change.
+        self.slint_state.set_needs_redraw(
+            frame_changed || has_overlay || self.slint_window.has_active_animations(),
+        );
+
+        // The last frame is still on screen, so there's nothing to present unless a snapshot of
+        // it was requested
+        let snapshot_requests = self.slint_state.take_snapshot_requests();
+        if self.options.redraw_on_demand && !drawn && !has_overlay && snapshot_requests.is_empty()
+        {
+            return;
+        }
+        if has_overlay {
+            let mut overlay_buffer = self.overlay_buffer.borrow_mut();
+            overlay_buffer.resize(self.render_width, self.render_height);
+            overlay_buffer
+                .pixels_mut()
+                .copy_from_slice(self.pixel_buffer.borrow().pixels());
+
+            let mut frame = SlintOverlayFrame {
+                pixels: overlay_buffer.pixels_mut(),
+                width: self.render_width,
+                height: self.render_height,
+                scale_factor: self.slint_window.scale_factor(),
+            };
+            if let Some(overlay) = &overlay {
+                overlay(&mut frame);
+            }
+            if self.software_cursor {
+                let position = *self.last_mouse_position.borrow();
+                draw_software_cursor(&mut frame, position.x, position.y);
+            }
+        }
+
+        let pixel_buffer = if has_overlay {
+            self.overlay_buffer.borrow()
+        } else {
+            self.pixel_buffer.borrow()
+        };
+        let render_size = (self.render_width, self.render_height);
+        let physical_size = (self.physical_width, self.physical_height);
+
+        let dirty_region = match dirty_bounds {
+            _ if full_repaint || has_overlay => SlintDirtyRegion {
+                x: 0,
+                y: 0,
+                width: self.physical_width,
+                height: self.physical_height,
+            },
+            Some((origin, size)) => {
+                dirty_region_in_window(origin, size, render_size, physical_size)
+            }
+            None => SlintDirtyRegion {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            },
+        };
+        self.slint_state.set_last_dirty_region(dirty_region);
+
+        // Blit to the custom render target or to softbuffer. Snapshots are taken from the final
+        // buffer so they match what's on screen exactly.
+        if let Some(render_target) = &self.render_target {
+            self.target_frame
+                .resize(self.physical_width as usize * self.physical_height as usize, 0);
+            blit_frame(pixel_buffer.pixels(), render_size, &mut self.target_frame, physical_size);
+            send_snapshots(snapshot_requests, &self.target_frame, physical_size);
+            render_target.present(&self.target_frame, self.physical_width, self.physical_height);
+        } else if let Some(surface) = &mut self.sb_surface {
+            let result = surface.buffer_mut().and_then(|mut buffer| {
+                blit_frame(pixel_buffer.pixels(), render_size, &mut buffer, physical_size);
+                send_snapshots(snapshot_requests, &buffer, physical_size);
+                buffer.present()
+            });
+
+            // A failed present is retried with the next frame. The surface is only recreated in
+            // `on_frame()` once presents keep failing, since that needs the window.
+            let result = result.map_err(|e| e.to_string());
+            if let Some(error) = self.present_health.record(result) {
+                self.slint_state.report_error(error);
             }
-            // Don't unwrap - just ignore present errors
-            let _ = buffer.present();
         }
     }
 }
 
-impl<C: slint::ComponentHandle + 'static> baseview::WindowHandler for SlintWindowHandler<C> {
+impl<C: crate::SlintComponent> baseview::WindowHandler for SlintWindowHandler<C> {
     fn on_frame(&mut self, window: &mut baseview::Window) {
         // Wrap everything in catch_unwind to prevent panics from aborting in C callback
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            // A newer window replaced this one, so it shouldn't keep rendering the same state
+            if !self.spawn_token.is_current() {
+                if !self.closed {
+                    info_log!("The editor was spawned again, closing the old window");
+                    self.handle_close();
+                    self.end_unbounded_movement(window);
+                    window.close();
+                }
+                return;
+            }
+
             // Poll for mouse control requests
             self.process_cursor_requests(window);
+            self.apply_requested_resize(window);
+            if let Some(opacity) = self.slint_state.take_window_opacity_change() {
+                set_window_opacity(window, opacity);
+            }
+
+            let mut window_access = SlintWindowAccess::new(window);
+            self.slint_state.run_window_requests(&mut window_access);
+            if window_access.close_requested() {
+                info_log!("The plugin closed the editor's window");
+                self.handle_close();
+                self.end_unbounded_movement(window);
+                window.close();
+                return;
+            }
 
             self.on_frame_inner();
+
+            if self.present_health.take_recreation_request() {
+                self.recreate_softbuffer_surface(window);
+            }
         }));
 
         if let Err(e) = result {
-            debug_log(&format!("PANIC in on_frame: {:?}", e));
+            error_log!("PANIC in on_frame: {:?}", e);
         }
     }
 
@@ -310,6 +1036,21 @@ impl<C: slint::ComponentHandle + 'static> baseview::WindowHandler for SlintWindo
     ) -> baseview::EventStatus {
         // Wrap in catch_unwind to prevent panics from aborting in C callback
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            // A release or a focus change always ends a drag, whether or not the component
+            // noticed. The release may have happened outside of the window. Closing the window
+            // mid-drag ends it too, since the cursor would otherwise stay hidden or confined after
+            // the window is gone.
+            let ends_drag = matches!(
+                event,
+                baseview::Event::Mouse(baseview::MouseEvent::ButtonReleased { .. })
+                    | baseview::Event::Window(
+                        baseview::WindowEvent::Unfocused | baseview::WindowEvent::WillClose
+                    )
+            );
+
+            // Slint counts clicks with the platform's interval, which all editors on this thread
+            // share
+            set_click_interval(self.options.double_click_interval);
             let status = self.on_event_inner(event);
 
             // Process cursor control requests immediately after event dispatch.
@@ -317,6 +1058,10 @@ impl<C: slint::ComponentHandle + 'static> baseview::WindowHandler for SlintWindo
             // the cursor restoration happens immediately rather than waiting for
             // the next on_frame() call (which may be delayed up to 15ms or more).
             self.process_cursor_requests(window);
+            if ends_drag {
+                self.end_unbounded_movement(window);
+            }
+            self.apply_requested_resize(window);
 
             status
         }));
@@ -324,63 +1069,141 @@ impl<C: slint::ComponentHandle + 'static> baseview::WindowHandler for SlintWindo
         match result {
             Ok(status) => status,
             Err(e) => {
-                debug_log(&format!("PANIC in on_event: {:?}", e));
+                error_log!("PANIC in on_event: {:?}", e);
                 baseview::EventStatus::Ignored
             }
         }
     }
 }
 
-impl<C: slint::ComponentHandle + 'static> SlintWindowHandler<C> {
+impl<C: crate::SlintComponent> Drop for SlintWindowHandler<C> {
+    fn drop(&mut self) {
+        // Without `WillClose` there's no window left to restore the cursor with
+        if *self.unbounded_active.borrow() {
+            warn_log!("The window was destroyed during unbounded mouse movement");
+        }
+
+        // Not all platforms send `WillClose`, so the component may not have been notified yet
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.handle_close()));
+        if let Err(e) = result {
+            error_log!("PANIC while closing: {:?}", e);
+        }
+    }
+}
+
+impl<C: crate::SlintComponent> SlintWindowHandler<C> {
     fn on_event_inner(&mut self, event: baseview::Event) -> baseview::EventStatus {
+        if self.closed {
+            return baseview::EventStatus::Ignored;
+        }
+
+        if let baseview::Event::Window(baseview::WindowEvent::WillClose) = &event {
+            self.handle_close();
+            return baseview::EventStatus::Captured;
+        }
+
+        // Late events after the component went away are dropped instead of being dispatched into
+        // an empty window
+        if !has_live_component(&self.slint_window) {
+            trace_log!("Ignoring an event without a live component");
+            return baseview::EventStatus::Ignored;
+        }
+
+        // Editor-wide shortcuts take precedence over everything else
+        if let baseview::Event::Keyboard(keyboard_event) = &event {
+            if self.slint_state.handle_shortcut(keyboard_event) {
+                self.update_modifiers(keyboard_event.modifiers);
+                return baseview::EventStatus::Captured;
+            }
+            if let Some(focus_ring) = &self.options.focus_ring {
+                if focus_ring.handle_key(keyboard_event)
+                    || focus_ring.nudge(keyboard_event, self.gui_context.as_ref())
+                {
+                    self.update_modifiers(keyboard_event.modifiers);
+                    return baseview::EventStatus::Captured;
+                }
+            }
+        }
+
         // Handle window resize specially
         if let baseview::Event::Window(baseview::WindowEvent::Resized(window_info)) = &event {
             let logical_size = window_info.logical_size();
             let physical_size = window_info.physical_size();
             let new_scale_factor = window_info.scale() as f32;
 
-            debug_log(&format!(
+            info_log!(
                 "RESIZE: logical={}x{}, physical={}x{}, scale={}, old_scale={}",
                 logical_size.width, logical_size.height,
                 physical_size.width, physical_size.height,
                 new_scale_factor, self.scale_factor
-            ));
+            );
+
+            self.pending_initial_resize = None;
 
-            self.slint_state.size.store((
+            // The window is laid out at the size it actually has. If that's off the resize step,
+            // the snapped size is requested from the host and takes effect with the next resize.
+            let size = (
                 logical_size.width.round() as u32,
                 logical_size.height.round() as u32,
-            ));
+            );
+            self.slint_state.set_window_size(size);
+            let snapped_size = self.slint_state.snap_to_resize_step(size);
+            if snapped_size != size {
+                debug_log!(
+                    "Snapping the window size from {}x{} to {}x{}",
+                    size.0, size.1, snapped_size.0, snapped_size.1
+                );
+                let scale = self.slint_state.user_scale_factor();
+                self.slint_state.request_resize(
+                    (snapped_size.0 as f64 / scale).round() as u32,
+                    (snapped_size.1 as f64 / scale).round() as u32,
+                );
+            }
 
+            let physical_size_changed = (physical_size.width, physical_size.height)
+                != (self.physical_width, self.physical_height);
             self.physical_width = physical_size.width;
             self.physical_height = physical_size.height;
+            let render_scale = self.options.effective_render_scale();
+            self.render_width = render_dimension(self.physical_width, render_scale);
+            self.render_height = render_dimension(self.physical_height, render_scale);
 
             // Update scale factor from actual window info (fixes Retina display rendering)
             if (new_scale_factor - self.scale_factor).abs() > 0.001 {
-                debug_log(&format!("Updating scale factor from {} to {}", self.scale_factor, new_scale_factor));
+                debug_log!("Updating scale factor from {} to {}", self.scale_factor, new_scale_factor);
                 self.scale_factor = new_scale_factor;
+                self.mouse_control.set_scale_factor(new_scale_factor);
                 // Inform Slint of the scale factor change
                 self.slint_window.dispatch_event(
                     slint::platform::WindowEvent::ScaleFactorChanged {
-                        scale_factor: new_scale_factor,
+                        scale_factor: new_scale_factor * render_scale,
                     },
                 );
             }
 
             // Resize softbuffer surface
-            if let (Some(w), Some(h)) = (
+            if let (Some(surface), Some(w), Some(h)) = (
+                &mut self.sb_surface,
                 NonZeroU32::new(self.physical_width),
                 NonZeroU32::new(self.physical_height),
             ) {
-                let _ = self.sb_surface.resize(w, h);
+                if surface.resize(w, h).is_ok() && physical_size_changed {
+                    self.slint_state
+                        .notify_surface_changed(self.physical_width, self.physical_height);
+                }
+            } else if self.render_target.is_some() && physical_size_changed {
+                self.slint_state
+                    .notify_surface_changed(self.physical_width, self.physical_height);
             }
 
             // Resize pixel buffer
-            let pixel_count = (self.physical_width * self.physical_height) as usize;
-            self.pixel_buffer.borrow_mut().resize(pixel_count, slint::Rgb8Pixel::default());
+            self.pixel_buffer
+                .borrow_mut()
+                .resize(self.render_width, self.render_height);
 
             // Update Slint window size
             self.slint_window
-                .set_size(PhysicalSize::new(self.physical_width, self.physical_height));
+                .set_size(PhysicalSize::new(self.render_width, self.render_height));
 
             // Also dispatch a Resized event with logical size to ensure layout is recomputed
             self.slint_window.dispatch_event(slint::platform::WindowEvent::Resized {
@@ -390,19 +1213,100 @@ impl<C: slint::ComponentHandle + 'static> SlintWindowHandler<C> {
                 ),
             });
 
-            // Request a redraw after resize
+            self.publish_scale_info();
+
+            // Paint the new size right away so the resized surface never shows stale or
+            // uninitialized contents until the next frame
             self.slint_window.request_redraw();
+            self.static_render_pending = true;
+            if !self.painted_during_resize && !self.slint_state.is_suspended() {
+                self.painted_during_resize = true;
+                self.paint();
+            }
         }
 
         // Track mouse position for events that need it
-        if let baseview::Event::Mouse(baseview::MouseEvent::CursorMoved { position, .. }) = &event {
+        if let baseview::Event::Mouse(baseview::MouseEvent::CursorMoved { position: raw, .. }) =
+            &event
+        {
             // On macOS, baseview reports coordinates in logical (post-scaled) units,
             // so we should NOT divide by scale_factor. The coordinates are already correct.
             // In unbounded mode, baseview now handles delta tracking and reports virtual positions.
-            let logical_x = (position.x as f32).max(0.0);
-            let logical_y = (position.y as f32).max(0.0);
+            // Positions are never rounded, so freehand drawing gets the full sub-pixel precision.
+            let mut position = LogicalPosition::new(raw.x as f32, raw.y as f32);
+
+            // The virtual position is moved by baseview's deltas and confined to the configured
+            // region, so moving back from an edge doesn't first have to undo the overshoot. The
+            // deltas are taken before converting to `f32` so they stay precise far from the origin.
+            if let Some((origin, (last_x, last_y))) = self.unbounded_confinement {
+                let last_position = *self.last_mouse_position.borrow();
+                let moved = LogicalPosition::new(
+                    last_position.x + (raw.x - last_x) as f32,
+                    last_position.y + (raw.y - last_y) as f32,
+                );
+                self.unbounded_confinement = Some((origin, (raw.x, raw.y)));
+                position = self.options.unbounded_region.confine(
+                    moved,
+                    origin,
+                    (
+                        self.physical_width as f32 / self.scale_factor,
+                        self.physical_height as f32 / self.scale_factor,
+                    ),
+                );
+            }
+
+            // While hovering the position is clamped to the window so elements at the window's
+            // edges don't get stuck in a weird hover state. During a drag the position is passed
+            // through as is so sliders keep tracking the cursor after it leaves the window.
+            let is_dragging = *self.mouse_button_pressed.borrow();
+            *self.last_mouse_position.borrow_mut() =
+                if is_dragging && !self.options.clamp_pointer_during_drag {
+                    position
+                } else {
+                    self.clamp_to_window(position)
+                };
+            let position = *self.last_mouse_position.borrow();
+            self.mouse_control.set_position(position.x, position.y);
+        }
+
+        // Keep track of the held modifiers so the component can query them
+        match &event {
+            baseview::Event::Mouse(
+                baseview::MouseEvent::CursorMoved { modifiers, .. }
+                | baseview::MouseEvent::ButtonPressed { modifiers, .. }
+                | baseview::MouseEvent::ButtonReleased { modifiers, .. }
+                | baseview::MouseEvent::WheelScrolled { modifiers, .. },
+            ) => {
+                // Modifier key events can be missing or lost, so any change is sent as modifier key
+                // events before the pointer event itself
+                for key_event in modifier_changes(self.mouse_control.modifiers(), *modifiers) {
+                    self.slint_window.dispatch_event(key_event);
+                }
+                self.update_modifiers(*modifiers)
+            }
+            baseview::Event::Keyboard(keyboard_event) => {
+                self.update_modifiers(keyboard_event.modifiers)
+            }
+            _ => (),
+        }
+
+        // Keep track of the wheel steps before Slint's scroll handlers get to run
+        if let baseview::Event::Mouse(baseview::MouseEvent::WheelScrolled { delta, .. }) = &event {
+            self.mouse_control.add_scroll(scroll_delta(delta));
+        }
 
-            *self.last_mouse_position.borrow_mut() = LogicalPosition::new(logical_x, logical_y);
+        // Keep track of whether the cursor is inside of the window. Not all platforms send
+        // `CursorEntered`, so any cursor movement also counts as entering the window. The enter
+        // event itself doesn't carry a position, so Slint only learns about the cursor's position
+        // once the first `CursorMoved` event arrives.
+        match &event {
+            baseview::Event::Mouse(
+                baseview::MouseEvent::CursorEntered | baseview::MouseEvent::CursorMoved { .. },
+            ) => *self.cursor_in_window.borrow_mut() = true,
+            baseview::Event::Mouse(baseview::MouseEvent::CursorLeft) => {
+                *self.cursor_in_window.borrow_mut() = false
+            }
+            _ => (),
         }
 
         // Track mouse button state for drag-outside-window handling
@@ -413,46 +1317,368 @@ impl<C: slint::ComponentHandle + 'static> SlintWindowHandler<C> {
             *self.mouse_button_pressed.borrow_mut() = false;
         }
 
+        match &event {
+            baseview::Event::Window(baseview::WindowEvent::Focused) => {
+                self.slint_state.set_focused(true)
+            }
+            baseview::Event::Window(baseview::WindowEvent::Unfocused) => {
+                self.slint_state.set_focused(false)
+            }
+            _ => (),
+        }
+
+        // If the window loses focus mid-drag then the button was most likely released outside of
+        // the window without us being notified. Let Slint know so it doesn't get stuck in a drag.
+        if let baseview::Event::Window(baseview::WindowEvent::Unfocused) = &event {
+            if self.mouse_button_pressed.replace(false) {
+                let position = *self.last_mouse_position.borrow();
+                let _ = self.slint_window.try_dispatch_event(
+                    slint::platform::WindowEvent::PointerReleased {
+                        position,
+                        button: slint::platform::PointerEventButton::Left,
+                    },
+                );
+            }
+        }
+
         // Translate and dispatch the event
+        let is_release = matches!(
+            event,
+            baseview::Event::Mouse(baseview::MouseEvent::ButtonReleased { .. })
+        );
         let is_button_pressed = *self.mouse_button_pressed.borrow();
-        if let Some(mut slint_event) = translate_event(&event, self.scale_factor, is_button_pressed) {
+        let slint_event = match (&event, &self.options.key_mapping) {
+            (baseview::Event::Keyboard(keyboard_event), Some(key_mapping)) => {
+                translate_mapped_key_event(keyboard_event, key_mapping)
+            }
+            _ => None,
+        }
+        .or_else(|| translate_event(&event, self.scale_factor, is_button_pressed));
+        if let Some(mut slint_event) = slint_event {
             // Fill in mouse position for events that need it
             let last_pos = *self.last_mouse_position.borrow();
-            match &mut slint_event {
-                slint::platform::WindowEvent::PointerPressed { position, .. } => {
-                    *position = last_pos;
-                }
-                slint::platform::WindowEvent::PointerReleased { position, .. } => {
-                    *position = last_pos;
-                }
-                slint::platform::WindowEvent::PointerScrolled { position, .. } => {
-                    *position = last_pos;
+            set_pointer_position(&mut slint_event, last_pos);
+
+            // During a smoothed drag, movement only updates the target and the frames dispatch
+            // the smoothed positions. Presses and releases always happen at the exact position.
+            if let Some(pointer_smoother) = &mut self.pointer_smoother {
+                use slint::platform::WindowEvent;
+
+                match slint_event {
+                    WindowEvent::PointerMoved { position } if is_button_pressed => {
+                        pointer_smoother.set_target(position);
+                        return baseview::EventStatus::Captured;
+                    }
+                    WindowEvent::PointerReleased { .. } => {
+                        if let Some(position) = pointer_smoother.finish() {
+                            dispatch_to_component(
+                                &self.slint_window,
+                                WindowEvent::PointerMoved { position },
+                            );
+                        }
+                    }
+                    WindowEvent::PointerMoved { .. } | WindowEvent::PointerPressed { .. } => {
+                        pointer_smoother.reset(last_pos)
+                    }
+                    _ => (),
                 }
-                _ => {}
             }
 
-            // Use try_dispatch_event to catch any errors
-            match self.slint_window.try_dispatch_event(slint_event) {
-                Ok(()) => {}
-                Err(e) => {
-                    debug_log(&format!("Event dispatch error: {:?}", e));
-                }
+            // Slint picks the element a drag belongs to from the pointer's position, so make sure
+            // it has seen the pointer at the press position. Otherwise a press that arrives before
+            // any movement, like the first click after the window appeared, could start a drag in
+            // the wrong element or none at all.
+            if matches!(slint_event, slint::platform::WindowEvent::PointerPressed { .. }) {
+                let _ = self
+                    .slint_window
+                    .try_dispatch_event(slint::platform::WindowEvent::PointerMoved {
+                        position: last_pos,
+                    });
+            }
+
+            dispatch_to_component(&self.slint_window, slint_event);
+
+            // `PointerExited` is suppressed while dragging, so if the drag ended outside of the
+            // window then the exit needs to be sent now to clear any hover state
+            if is_release && !*self.cursor_in_window.borrow() {
+                let _ = self
+                    .slint_window
+                    .try_dispatch_event(slint::platform::WindowEvent::PointerExited);
             }
 
             // Process timers/animations after event dispatch - this may be needed
             // for Slint to fully process the event
-            slint::platform::update_timers_and_animations();
+            update_timers_and_animations(&self.slint_state);
 
             // Request a redraw after processing events
             self.slint_window.request_redraw();
 
-            baseview::EventStatus::Captured
+            // Slint only needs to know about modifier keys, the host may still use them for its
+            // own shortcuts
+            match &event {
+                baseview::Event::Keyboard(e) if is_modifier_key(&e.key) => {
+                    baseview::EventStatus::Ignored
+                }
+                _ => baseview::EventStatus::Captured,
+            }
         } else {
             baseview::EventStatus::Ignored
         }
     }
 }
 
+/// Whether `window` still shows a component. A shown component is kept alive by its window, so
+/// this only becomes false once the component has been hidden, after which it may also have been
+/// dropped.
+fn has_live_component(window: &MinimalSoftwareWindow) -> bool {
+    window.window().is_visible()
+}
+
+/// Dispatch `event` to the component shown in `window`. Returns `false` without dispatching
+/// anything if there is no live component. Dispatch errors are logged.
+fn dispatch_to_component(
+    window: &MinimalSoftwareWindow,
+    event: slint::platform::WindowEvent,
+) -> bool {
+    if !has_live_component(window) {
+        return false;
+    }
+
+    match window.try_dispatch_event(event) {
+        Ok(()) => true,
+        Err(e) => {
+            warn_log!("Event dispatch error: {:?}", e);
+            false
+        }
+    }
+}
+
+/// Compute the size Slint should render at for a physical window dimension.
+fn render_dimension(physical: u32, render_scale: f32) -> u32 {
+    ((physical as f32 * render_scale).round() as u32).max(1)
+}
+
+/// Convert a Slint pixel to softbuffer's `0x00RRGGBB` format.
+#[inline]
+fn pixel_to_softbuffer(pixel: slint::Rgb8Pixel) -> u32 {
+    ((pixel.r as u32) << 16) | ((pixel.g as u32) << 8) | pixel.b as u32
+}
+
+/// Convert the bounding box of a region Slint redrew in the `render_size` buffer to the
+/// `physical_size` window, rounding outwards so the result covers every changed pixel.
+fn dirty_region_in_window(
+    origin: slint::PhysicalPosition,
+    size: PhysicalSize,
+    render_size: (u32, u32),
+    physical_size: (u32, u32),
+) -> SlintDirtyRegion {
+    if size.width == 0 || size.height == 0 || render_size.0 == 0 || render_size.1 == 0 {
+        return SlintDirtyRegion {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        };
+    }
+
+    let scale = |value: i64, render: u32, physical: u32, round_up: bool| {
+        let value = value.clamp(0, render as i64) as u64 * physical as u64;
+        let scaled = if round_up {
+            value.div_ceil(render as u64)
+        } else {
+            value / render as u64
+        };
+        scaled as u32
+    };
+    let x = scale(origin.x as i64, render_size.0, physical_size.0, false);
+    let y = scale(origin.y as i64, render_size.1, physical_size.1, false);
+    let right = origin.x as i64 + size.width as i64;
+    let bottom = origin.y as i64 + size.height as i64;
+
+    SlintDirtyRegion {
+        x,
+        y,
+        width: scale(right, render_size.0, physical_size.0, true) - x,
+        height: scale(bottom, render_size.1, physical_size.1, true) - y,
+    }
+}
+
+/// Copy the `src_size` rendered frame to a `dst_size` buffer in softbuffer's format, resampling
+/// it if the sizes differ.
+fn blit_frame(
+    src: &[slint::Rgb8Pixel],
+    src_size: (u32, u32),
+    dst: &mut [u32],
+    dst_size: (u32, u32),
+) {
+    if src_size == dst_size {
+        for (dst, src) in dst.iter_mut().zip(src) {
+            *dst = pixel_to_softbuffer(*src);
+        }
+    } else {
+        blit_resampled(src, src_size, dst, dst_size);
+    }
+}
+
+/// Draw the splash frame at the window's physical size and present it to the custom render target
+/// or to softbuffer. The component's first frame replaces it, since that's always rendered in full.
+fn present_splash(
+    splash: &SlintSplash,
+    sb_surface: Option<
+        &mut softbuffer::Surface<SoftbufferWindowHandleAdapter, SoftbufferWindowHandleAdapter>,
+    >,
+    render_target: Option<&dyn SlintRenderTarget>,
+    physical_size: (u32, u32),
+    scale_factor: f32,
+) {
+    let (width, height) = physical_size;
+    let mut frame = vec![Rgb8Pixel::default(); width as usize * height as usize];
+    splash.draw(&mut frame, width, height, scale_factor);
+
+    if let Some(render_target) = render_target {
+        let mut target_frame = vec![0; frame.len()];
+        blit_frame(&frame, physical_size, &mut target_frame, physical_size);
+        render_target.present(&target_frame, width, height);
+    } else if let Some(Ok(mut buffer)) = sb_surface.map(|surface| surface.buffer_mut()) {
+        blit_frame(&frame, physical_size, &mut buffer, physical_size);
+        let _ = buffer.present();
+    }
+    debug_log!("Splash frame presented");
+}
+
+/// The number of presents in a row that need to fail before the softbuffer surface is considered
+/// lost and gets recreated. Single failures happen while a window is being resized or hidden.
+const MAX_PRESENT_FAILURES: u32 = 30;
+
+/// The number of times the softbuffer surface is recreated without a successful present in
+/// between before giving up and reporting an error.
+const MAX_SURFACE_RECREATIONS: u32 = 3;
+
+/// Keeps track of failed presents to detect when the softbuffer surface has been lost.
+#[derive(Debug, Default)]
+struct PresentHealth {
+    /// The number of presents that failed in a row.
+    failures: u32,
+    /// The number of times the surface was recreated since the last successful present.
+    recreations: u32,
+    /// Whether the surface should be recreated before the next frame.
+    recreation_requested: bool,
+    /// The number of upcoming presents that are treated as failed regardless of their result. See
+    /// `NIH_PLUG_SLINT_SIMULATE_PRESENT_FAILURES`.
+    simulated_failures: u32,
+}
+
+impl PresentHealth {
+    fn new(simulated_failures: u32) -> Self {
+        Self {
+            simulated_failures,
+            ..Default::default()
+        }
+    }
+
+    /// Record whether a frame could be presented. Once [`MAX_PRESENT_FAILURES`] presents failed in
+    /// a row, the surface should be recreated. If that didn't help after
+    /// [`MAX_SURFACE_RECREATIONS`] attempts, this returns the error to report, but only once.
+    fn record(&mut self, result: Result<(), String>) -> Option<SlintEditorError> {
+        let result = if self.simulated_failures > 0 {
+            self.simulated_failures -= 1;
+            Err(String::from("simulated failure"))
+        } else {
+            result
+        };
+
+        let Err(message) = result else {
+            self.failures = 0;
+            self.recreations = 0;
+            return None;
+        };
+
+        self.failures += 1;
+        if self.failures != MAX_PRESENT_FAILURES {
+            None
+        } else if self.recreations < MAX_SURFACE_RECREATIONS {
+            self.recreation_requested = true;
+            None
+        } else {
+            Some(SlintEditorError::SoftbufferPresent(message))
+        }
+    }
+
+    /// Whether the surface should be recreated now. This also resets the failure count for the
+    /// new surface.
+    fn take_recreation_request(&mut self) -> bool {
+        if !std::mem::take(&mut self.recreation_requested) {
+            return false;
+        }
+
+        self.failures = 0;
+        self.recreations += 1;
+        true
+    }
+}
+
+/// Pass a copy of the presented frame to all pending snapshot callbacks.
+fn send_snapshots(
+    callbacks: Vec<Box<dyn FnOnce(SharedPixelBuffer<Rgb8Pixel>) + Send>>,
+    buffer: &[u32],
+    (width, height): (u32, u32),
+) {
+    if callbacks.is_empty() {
+        return;
+    }
+
+    let snapshot = softbuffer_to_pixels(buffer, width, height);
+    for callback in callbacks {
+        callback(snapshot.clone());
+    }
+}
+
+/// Convert a softbuffer buffer back to RGB pixels.
+fn softbuffer_to_pixels(buffer: &[u32], width: u32, height: u32) -> SharedPixelBuffer<Rgb8Pixel> {
+    let mut pixels = SharedPixelBuffer::<Rgb8Pixel>::new(width, height);
+    for (pixel, &value) in pixels.make_mut_slice().iter_mut().zip(buffer) {
+        *pixel = Rgb8Pixel::new((value >> 16) as u8, (value >> 8) as u8, value as u8);
+    }
+
+    pixels
+}
+
+/// Copy a `src_size` image to a `dst_size` softbuffer buffer. Every destination pixel is the
+/// average of the source pixels it covers, so this acts as a box filter when downscaling and as
+/// nearest neighbor sampling when upscaling.
+fn blit_resampled(
+    src: &[slint::Rgb8Pixel],
+    (src_width, src_height): (u32, u32),
+    dst: &mut [u32],
+    (dst_width, dst_height): (u32, u32),
+) {
+    let (src_width, src_height) = (src_width as usize, src_height as usize);
+    let (dst_width, dst_height) = (dst_width as usize, dst_height as usize);
+    let src_range = |dst_pos: usize, src_len: usize, dst_len: usize| {
+        let start = dst_pos * src_len / dst_len;
+        let end = ((dst_pos + 1) * src_len / dst_len).clamp(start + 1, src_len);
+        start..end
+    };
+
+    for y in 0..dst_height {
+        let src_ys = src_range(y, src_height, dst_height);
+        for x in 0..dst_width {
+            let src_xs = src_range(x, src_width, dst_width);
+            let (mut r, mut g, mut b, mut count) = (0u32, 0u32, 0u32, 0u32);
+            for src_y in src_ys.clone() {
+                for pixel in &src[src_y * src_width + src_xs.start..src_y * src_width + src_xs.end] {
+                    r += pixel.r as u32;
+                    g += pixel.g as u32;
+                    b += pixel.b as u32;
+                    count += 1;
+                }
+            }
+
+            dst[y * dst_width + x] = ((r / count) << 16) | ((g / count) << 8) | (b / count);
+        }
+    }
+}
+
 /// Softbuffer uses raw_window_handle v6, but baseview uses raw_window_handle v5, so we need to
 /// adapt it ourselves.
 #[derive(Clone)]
@@ -485,15 +1711,49 @@ impl raw_window_handle_06::HasWindowHandle for SoftbufferWindowHandleAdapter {
     }
 }
 
+/// Create a softbuffer context and a surface for `window`, sized to the window's physical size.
+fn create_softbuffer_surface(
+    window: &baseview::Window<'_>,
+    physical_width: u32,
+    physical_height: u32,
+) -> Result<
+    (
+        softbuffer::Context<SoftbufferWindowHandleAdapter>,
+        softbuffer::Surface<SoftbufferWindowHandleAdapter, SoftbufferWindowHandleAdapter>,
+    ),
+    SlintEditorError,
+> {
+    debug_log!("Creating softbuffer context...");
+    let target = baseview_window_to_surface_target(window)?;
+    let context = softbuffer::Context::new(target.clone())
+        .map_err(|e| SlintEditorError::SoftbufferContext(e.to_string()))?;
+    debug_log!("Softbuffer context created successfully");
+
+    debug_log!("Creating softbuffer surface...");
+    let mut surface = softbuffer::Surface::new(&context, target)
+        .map_err(|e| SlintEditorError::SoftbufferSurface(e.to_string()))?;
+    surface
+        .resize(
+            NonZeroU32::new(physical_width).unwrap_or(NonZeroU32::MIN),
+            NonZeroU32::new(physical_height).unwrap_or(NonZeroU32::MIN),
+        )
+        .map_err(|e| SlintEditorError::SoftbufferSurface(e.to_string()))?;
+    debug_log!("Softbuffer surface created successfully");
+
+    Ok((context, surface))
+}
+
+/// Translate baseview's raw_window_handle v5 handles to their v6 equivalents. Returns an error
+/// describing the handle if baseview reports a windowing system that softbuffer can't draw to.
 fn baseview_window_to_surface_target(
     window: &baseview::Window<'_>,
-) -> SoftbufferWindowHandleAdapter {
+) -> Result<SoftbufferWindowHandleAdapter, SlintEditorError> {
     use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
     let raw_display_handle = window.raw_display_handle();
     let raw_window_handle = window.raw_window_handle();
 
-    SoftbufferWindowHandleAdapter {
+    Ok(SoftbufferWindowHandleAdapter {
         raw_display_handle: match raw_display_handle {
             raw_window_handle::RawDisplayHandle::AppKit(_) => {
                 raw_window_handle_06::RawDisplayHandle::AppKit(
@@ -521,13 +1781,33 @@ fn baseview_window_to_surface_target(
                     raw_window_handle_06::WindowsDisplayHandle::new(),
                 )
             }
-            _ => panic!("Unsupported display handle type"),
+            raw_window_handle::RawDisplayHandle::Wayland(handle) => {
+                raw_window_handle_06::RawDisplayHandle::Wayland(
+                    raw_window_handle_06::WaylandDisplayHandle::new(
+                        NonNull::new(handle.display).ok_or_else(|| {
+                            SlintEditorError::UnsupportedWindowHandle(String::from(
+                                "baseview returned a null Wayland display",
+                            ))
+                        })?,
+                    ),
+                )
+            }
+            handle => {
+                return Err(SlintEditorError::UnsupportedWindowHandle(format!(
+                    "unsupported display handle type: {:?}",
+                    handle
+                )))
+            }
         },
         raw_window_handle: match raw_window_handle {
             raw_window_handle::RawWindowHandle::AppKit(handle) => {
                 raw_window_handle_06::RawWindowHandle::AppKit(
                     raw_window_handle_06::AppKitWindowHandle::new(
-                        NonNull::new(handle.ns_view).unwrap(),
+                        NonNull::new(handle.ns_view).ok_or_else(|| {
+                            SlintEditorError::UnsupportedWindowHandle(String::from(
+                                "baseview returned a null NSView",
+                            ))
+                        })?,
                     ),
                 )
             }
@@ -539,18 +1819,170 @@ fn baseview_window_to_surface_target(
             raw_window_handle::RawWindowHandle::Xcb(handle) => {
                 raw_window_handle_06::RawWindowHandle::Xcb(
                     raw_window_handle_06::XcbWindowHandle::new(
-                        NonZeroU32::new(handle.window).unwrap(),
+                        NonZeroU32::new(handle.window).ok_or_else(|| {
+                            SlintEditorError::UnsupportedWindowHandle(String::from(
+                                "baseview returned a null XCB window",
+                            ))
+                        })?,
                     ),
                 )
             }
             raw_window_handle::RawWindowHandle::Win32(handle) => {
                 let mut raw_handle = raw_window_handle_06::Win32WindowHandle::new(
-                    NonZeroIsize::new(handle.hwnd as isize).unwrap(),
+                    NonZeroIsize::new(handle.hwnd as isize).ok_or_else(|| {
+                        SlintEditorError::UnsupportedWindowHandle(String::from(
+                            "baseview returned a null HWND",
+                        ))
+                    })?,
                 );
                 raw_handle.hinstance = NonZeroIsize::new(handle.hinstance as isize);
                 raw_window_handle_06::RawWindowHandle::Win32(raw_handle)
             }
-            _ => panic!("Unsupported window handle type"),
+            raw_window_handle::RawWindowHandle::Wayland(handle) => {
+                raw_window_handle_06::RawWindowHandle::Wayland(
+                    raw_window_handle_06::WaylandWindowHandle::new(
+                        NonNull::new(handle.surface).ok_or_else(|| {
+                            SlintEditorError::UnsupportedWindowHandle(String::from(
+                                "baseview returned a null Wayland surface",
+                            ))
+                        })?,
+                    ),
+                )
+            }
+            handle => {
+                return Err(SlintEditorError::UnsupportedWindowHandle(format!(
+                    "unsupported window handle type: {:?}",
+                    handle
+                )))
+            }
         },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_window::show_in_test_window;
+    use std::sync::atomic::AtomicUsize;
+
+    slint::slint! {
+        export component PressTest inherits Window {
+            width: 100px;
+            height: 100px;
+
+            out property <bool> pressed: area.pressed;
+
+            area := TouchArea {}
+        }
+    }
+
+    #[test]
+    fn events_after_drop_are_ignored() {
+        let (window, ui) = show_in_test_window(|| PressTest::new().unwrap());
+
+        let press = || slint::platform::WindowEvent::PointerPressed {
+            position: LogicalPosition::new(50.0, 50.0),
+            button: slint::platform::PointerEventButton::Left,
+        };
+        assert!(dispatch_to_component(&window, press()));
+        assert!(ui.get_pressed());
+
+        ui.hide().unwrap();
+        drop(ui);
+        assert!(!has_live_component(&window));
+        assert!(!dispatch_to_component(&window, press()));
+    }
+
+    #[test]
+    fn timer_panics_are_reported() {
+        ensure_slint_platform().unwrap();
+        let slint_state = SlintState::from_size(100, 100);
+        slint::Timer::single_shot(Duration::ZERO, || panic!("Bad timer"));
+
+        update_timers_and_animations(&slint_state);
+        assert_eq!(
+            slint_state.last_error(),
+            Some(SlintEditorError::TimerCallback(String::from("Bad timer")))
+        );
+    }
+
+    #[test]
+    fn dirty_regions_are_scaled_to_the_window() {
+        let region = |x, y, width, height, render_size| {
+            dirty_region_in_window(
+                slint::PhysicalPosition::new(x, y),
+                PhysicalSize::new(width, height),
+                render_size,
+                (200, 100),
+            )
+        };
+        let expected = |x, y, width, height| SlintDirtyRegion {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        assert_eq!(region(10, 20, 30, 40, (200, 100)), expected(10, 20, 30, 40));
+        // A half resolution render buffer covers twice as many window pixels
+        assert_eq!(region(10, 20, 30, 15, (100, 50)), expected(20, 40, 60, 30));
+        // Partially covered window pixels are included
+        assert_eq!(region(1, 1, 1, 1, (300, 300)), expected(0, 0, 2, 1));
+        assert!(region(10, 20, 0, 40, (200, 100)).is_empty());
+    }
+
+    #[test]
+    fn param_changes_are_coalesced_per_frame() {
+        let params_changed = AtomicBool::new(false);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let callback: ParamChangedCallback<()> = Arc::new({
+            let calls = calls.clone();
+            move |_| {
+                calls.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        let frame = || notify_param_changes(&params_changed, Some(&callback), Some(&()));
+
+        // This is what `SlintEditor::param_value_changed()` does for every change from the host
+        for _ in 0..1000 {
+            params_changed.store(true, Ordering::Relaxed);
+        }
+        assert!(frame());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        assert!(!frame());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        params_changed.store(true, Ordering::Relaxed);
+        assert!(frame());
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn lost_surfaces_are_recreated() {
+        let attempts = MAX_SURFACE_RECREATIONS + 1;
+        let mut health = PresentHealth::new(MAX_PRESENT_FAILURES * attempts);
+
+        // The simulated failures override successful presents
+        for _ in 0..MAX_SURFACE_RECREATIONS {
+            for _ in 0..MAX_PRESENT_FAILURES {
+                assert!(!health.take_recreation_request());
+                assert_eq!(health.record(Ok(())), None);
+            }
+            assert!(health.take_recreation_request());
+        }
+
+        // The new surfaces didn't help, so the error is reported once
+        for _ in 1..MAX_PRESENT_FAILURES {
+            assert_eq!(health.record(Ok(())), None);
+        }
+        let error = SlintEditorError::SoftbufferPresent(String::from("simulated failure"));
+        assert_eq!(health.record(Ok(())), Some(error));
+        assert!(!health.take_recreation_request());
+
+        // Once presenting works again, the surface can be recovered later on
+        assert_eq!(health.record(Err(String::from("lost"))), None);
+        assert_eq!(health.record(Ok(())), None);
+        assert_eq!((health.failures, health.recreations), (0, 0));
     }
 }
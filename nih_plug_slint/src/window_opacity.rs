@@ -0,0 +1,33 @@
+//! Whole-window opacity for the platforms that support it for embedded windows.
+
+/// Set the opacity of the editor's window, with 0.0 being fully transparent and 1.0 being fully
+/// opaque. This is only implemented on macOS, where it sets the `alphaValue` of baseview's view.
+/// Plugin windows are embedded in a window owned by the host, and neither X11 nor Windows support
+/// this reliably for child windows. X11's `_NET_WM_WINDOW_OPACITY` only applies to top-level
+/// windows, and layered child windows on Windows only work if the host opted into them in its
+/// application manifest. On those platforms this is only logged at the debug level.
+#[cfg(target_os = "macos")]
+pub(crate) fn set_window_opacity(window: &baseview::Window<'_>, opacity: f32) {
+    use objc::runtime::Object;
+    use objc::{msg_send, sel, sel_impl};
+    use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+    let RawWindowHandle::AppKit(handle) = window.raw_window_handle() else {
+        return;
+    };
+    let ns_view = handle.ns_view as *mut Object;
+    if ns_view.is_null() {
+        return;
+    }
+
+    unsafe {
+        let () = msg_send![ns_view, setAlphaValue: opacity as f64];
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn set_window_opacity(_window: &baseview::Window<'_>, opacity: f32) {
+    use crate::logging::debug_log;
+
+    debug_log!("Window opacity {} is unsupported here", opacity);
+}
@@ -0,0 +1,159 @@
+//! Binding a two-dimensional control to a pair of parameters.
+
+use crossbeam::atomic::AtomicCell;
+use nih_plug::prelude::Param;
+use slint::{LogicalPosition, LogicalSize};
+use std::sync::Arc;
+
+use crate::{is_syncing_from_host, normalized_from_position, ControlAxis, SlintParamContext};
+
+/// Drives two parameters from a single XY pad, like a filter's cutoff on the horizontal axis and
+/// its resonance on the vertical axis. Both parameters are changed as part of one combined
+/// gesture: [`begin()`][Self::begin()] starts a gesture for both, [`drag()`][Self::drag()] or
+/// [`drag_to()`][Self::drag_to()] update them, and [`end()`][Self::end()] ends both gestures
+/// again. Clones share the same drag state.
+///
+/// While dragging, a parameter is only sent to the host when the pointer actually moved along its
+/// axis. If the other parameter is automated while the user drags straight along one axis, the
+/// automation keeps control over the other parameter instead of being overwritten with the value
+/// the drag started at. To reflect both the user's and the host's changes, position the pad's
+/// handle with [`position()`][Self::position()] from the parameter changed callback.
+///
+/// Like [`SlintParamContext`]'s setters, this does nothing while host-driven changes are applied
+/// to the component, see [`sync_from_host()`][crate::sync_from_host()].
+///
+/// # Example
+///
+/// ```ignore
+/// // In the .slint file, the pad's `TouchArea` calls `root.filter-pad-pressed()` and
+/// // `root.filter-pad-released()` from its `pointer-event` handler, and
+/// // `root.filter-pad-moved(self.mouse-x / self.width, 1 - self.mouse-y / self.height)` from its
+/// // `moved` handler. The handle is drawn at `root.filter-x` and `root.filter-y`.
+///
+/// // In the component factory:
+/// let filter_pad = SlintXyPad::new(SlintParamContext::new(gui_context.clone()));
+/// ui.on_filter_pad_pressed({
+///     let (filter_pad, params) = (filter_pad.clone(), params.clone());
+///     move || filter_pad.begin(&params.cutoff, &params.resonance)
+/// });
+/// ui.on_filter_pad_moved({
+///     let (filter_pad, params) = (filter_pad.clone(), params.clone());
+///     move |x, y| filter_pad.drag(&params.cutoff, &params.resonance, x, y)
+/// });
+/// ui.on_filter_pad_released({
+///     let (filter_pad, params) = (filter_pad.clone(), params.clone());
+///     move || filter_pad.end(&params.cutoff, &params.resonance)
+/// });
+///
+/// // In the parameter changed callback:
+/// let (x, y) = filter_pad.position(&params.cutoff, &params.resonance);
+/// ui.set_filter_x(x);
+/// ui.set_filter_y(y);
+/// ```
+#[derive(Clone)]
+pub struct SlintXyPad {
+    param_context: SlintParamContext,
+    /// The normalized values last sent for the horizontal and vertical parameters, or `None` if
+    /// no drag is in progress.
+    last_sent: Arc<AtomicCell<Option<(f32, f32)>>>,
+}
+
+impl std::fmt::Debug for SlintXyPad {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlintXyPad")
+            .field("last_sent", &self.last_sent.load())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SlintXyPad {
+    /// Create an XY pad that sets parameters through `param_context`.
+    pub fn new(param_context: SlintParamContext) -> Self {
+        Self {
+            param_context,
+            last_sent: Arc::new(AtomicCell::new(None)),
+        }
+    }
+
+    /// Whether a drag is currently in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.last_sent.load().is_some()
+    }
+
+    /// Start a gesture for both parameters. Call this when the pad is pressed. Does nothing if a
+    /// drag is already in progress.
+    pub fn begin<X: Param, Y: Param>(&self, x_param: &X, y_param: &Y) {
+        if is_syncing_from_host() || self.is_dragging() {
+            return;
+        }
+
+        let setter = self.param_context.setter();
+        setter.begin_set_parameter(x_param);
+        setter.begin_set_parameter(y_param);
+        self.last_sent.store(Some((
+            x_param.unmodulated_normalized_value(),
+            y_param.unmodulated_normalized_value(),
+        )));
+    }
+
+    /// Move the pad to the normalized `[0, 1]` values `x` and `y`, where `y` is 0 at the bottom of
+    /// the pad. Only the parameters whose axis changed since the last call are set. Does nothing
+    /// if [`begin()`][Self::begin()] was not called first.
+    pub fn drag<X: Param, Y: Param>(&self, x_param: &X, y_param: &Y, x: f32, y: f32) {
+        if is_syncing_from_host() {
+            return;
+        }
+        let Some((last_x, last_y)) = self.last_sent.load() else {
+            return;
+        };
+
+        let (x, y) = (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0));
+        let setter = self.param_context.setter();
+        if x != last_x {
+            setter.set_parameter_normalized(x_param, x);
+        }
+        if y != last_y {
+            setter.set_parameter_normalized(y_param, y);
+        }
+
+        self.last_sent.store(Some((x, y)));
+    }
+
+    /// Like [`drag()`][Self::drag()], but computes the values from a pointer `position` for a pad
+    /// whose top-left corner is at `pad_origin` and that is `pad_size` large. See
+    /// [`normalized_from_position()`] for how the coordinates are interpreted.
+    pub fn drag_to<X: Param, Y: Param>(
+        &self,
+        x_param: &X,
+        y_param: &Y,
+        position: LogicalPosition,
+        pad_origin: LogicalPosition,
+        pad_size: LogicalSize,
+    ) {
+        let x = normalized_from_position(position, pad_origin, pad_size, ControlAxis::Horizontal);
+        let y = normalized_from_position(position, pad_origin, pad_size, ControlAxis::Vertical);
+        self.drag(x_param, y_param, x, y);
+    }
+
+    /// End the gestures for both parameters. Call this when the pad is released. Does nothing if
+    /// no drag is in progress.
+    pub fn end<X: Param, Y: Param>(&self, x_param: &X, y_param: &Y) {
+        if is_syncing_from_host() || self.last_sent.swap(None).is_none() {
+            return;
+        }
+
+        let setter = self.param_context.setter();
+        setter.end_set_parameter(x_param);
+        setter.end_set_parameter(y_param);
+    }
+
+    /// The parameters' current unmodulated normalized values as the pad's `(x, y)` position, with
+    /// `y` being 0 at the bottom of the pad. Use this to position the pad's handle from the
+    /// parameter changed callback. This includes changes made by the host during a drag.
+    pub fn position<X: Param, Y: Param>(&self, x_param: &X, y_param: &Y) -> (f32, f32) {
+        (
+            x_param.unmodulated_normalized_value(),
+            y_param.unmodulated_normalized_value(),
+        )
+    }
+}